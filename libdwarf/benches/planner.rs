@@ -0,0 +1,202 @@
+//! Rough benchmark of the GOAP planner on a generated 100 action domain,
+//! next to the `State` based planner it replaced (see `baseline`).
+//!
+//! Run with `cargo bench -p libdwarf --bench planner`. At the time of
+//! writing it came out to:
+//!
+//! | plan         | bitset   | baseline  |
+//! |--------------|----------|-----------|
+//! | craft tier 1 | ~4µs     | ~38µs     |
+//! | craft tier 2 | ~130µs   | ~1.8ms    |
+//! | craft tier 3 | ~1.2ms   | ~116ms    |
+//! | unknown goal | ~0.1µs   | n/a       |
+use libdwarf::planner::{Condition, Planner, State};
+use std::time::{Duration, Instant};
+
+/// The planner as it was before conditions were interned: every node holds
+/// its own `State`, cloned for each action applied & hashed by iterating
+/// over the map.
+mod baseline {
+    use libdwarf::planner::{Condition, State};
+    use libpath::find_path_with;
+    use std::hash::{Hash, Hasher};
+
+    pub struct Action {
+        pub cost: usize,
+        pub pre: State,
+        pub post: State,
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct PlanNode {
+        pub last_action: Option<usize>,
+        pub state: State,
+    }
+
+    impl PlanNode {
+        fn num_mismatched(&self, state: &State) -> usize {
+            state
+                .iter()
+                .filter(|(condition, value)| self.state.get(condition) != Some(value))
+                .count()
+        }
+    }
+
+    impl Hash for PlanNode {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            for (key, value) in self.state.iter() {
+                key.hash(state);
+                value.hash(state);
+            }
+        }
+    }
+
+    impl PartialEq for PlanNode {
+        fn eq(&self, other: &Self) -> bool {
+            self.state == other.state
+        }
+    }
+
+    impl Eq for PlanNode {}
+
+    #[derive(Default)]
+    pub struct Planner {
+        pub actions: Vec<Action>,
+    }
+
+    impl Planner {
+        pub fn add_action(&mut self, pre: Vec<(Condition, bool)>, post: Vec<(Condition, bool)>) {
+            self.actions.push(Action {
+                cost: 1,
+                pre: pre.into_iter().collect(),
+                post: post.into_iter().collect(),
+            });
+        }
+
+        fn next_actions(&self, node: &PlanNode) -> Vec<(PlanNode, usize)> {
+            let mut potential = Vec::new();
+            for (idx, action) in self.actions.iter().enumerate() {
+                if node.num_mismatched(&action.pre) == 0 {
+                    let mut state = node.state.clone();
+                    for (condition, value) in action.post.iter() {
+                        state.insert(condition.clone(), *value);
+                    }
+
+                    potential.push((
+                        PlanNode {
+                            last_action: Some(idx),
+                            state,
+                        },
+                        action.cost,
+                    ));
+                }
+            }
+
+            potential
+        }
+
+        pub fn plan(&self, initial: &State, end: &State) -> Vec<usize> {
+            let start = PlanNode {
+                last_action: None,
+                state: initial.clone(),
+            };
+
+            let (_, plan) = find_path_with(
+                start,
+                |node| node.num_mismatched(end) == 0,
+                |node| node.num_mismatched(end),
+                |node| self.next_actions(node),
+            );
+
+            plan.iter().filter_map(|node| node.last_action).collect()
+        }
+    }
+}
+
+/// Number of crafting tiers in the domain.
+const TIERS: usize = 10;
+/// Number of items in each tier.
+const ITEMS: usize = 10;
+const ITERATIONS: u32 = 50;
+
+fn item(tier: usize, idx: usize) -> Condition {
+    Condition::Has(format!("item_{}_{}", tier, idx))
+}
+
+/// Each item is crafted from two items in the tier below it, which gives us
+/// TIERS * ITEMS actions. Returns the same domain for both planners.
+fn build_planners() -> (Planner, baseline::Planner) {
+    let mut planner = Planner::new();
+    let mut baseline = baseline::Planner::default();
+    for idx in 0..ITEMS {
+        let post = vec![(item(0, idx), true)];
+        planner
+            .add_action(format!("gather_{}", idx), 1, vec![], post.clone())
+            .unwrap();
+        baseline.add_action(vec![], post);
+    }
+
+    for tier in 1..TIERS {
+        for idx in 0..ITEMS {
+            let pre = vec![
+                (item(tier - 1, idx), true),
+                (item(tier - 1, (idx + 1) % ITEMS), true),
+            ];
+            let post = vec![(item(tier, idx), true)];
+            planner
+                .add_action(
+                    format!("craft_{}_{}", tier, idx),
+                    1,
+                    pre.clone(),
+                    post.clone(),
+                )
+                .unwrap();
+            baseline.add_action(pre, post);
+        }
+    }
+
+    (planner, baseline)
+}
+
+fn bench<F: FnMut() -> usize>(name: &str, mut func: F) {
+    let mut total = Duration::default();
+    let mut steps = 0;
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        steps = func();
+        total += start.elapsed();
+    }
+
+    println!(
+        "{:<24} {:>10.3?}/iter ({} step plan)",
+        name,
+        total / ITERATIONS,
+        steps
+    );
+}
+
+fn main() {
+    let (planner, baseline) = build_planners();
+
+    let mut initial = State::new();
+    for idx in 0..ITEMS {
+        initial.insert(item(0, idx), true);
+    }
+
+    for &tier in [1, 2, 3].iter() {
+        let mut desired = State::new();
+        desired.insert(item(tier, 0), true);
+        bench(&format!("craft tier {}", tier), || {
+            planner.plan(&initial, &desired).len()
+        });
+        bench(&format!("craft tier {} (baseline)", tier), || {
+            baseline.plan(&initial, &desired).len()
+        });
+    }
+
+    // No baseline for this one, it searches every reachable state before
+    // giving up & runs out of memory long before that.
+    let mut desired = State::new();
+    desired.insert(Condition::Has("unobtainium".to_string()), true);
+    bench("unknown goal", || planner.plan(&initial, &desired).len());
+}
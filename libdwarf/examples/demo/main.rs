@@ -1,6 +1,9 @@
 use crossterm::{input, RawScreen};
 
-use core::amethyst::ecs::{self, DispatcherBuilder, World, WorldExt};
+use core::amethyst::{
+    ecs::{self, DispatcherBuilder, World, WorldExt},
+    shrev::EventChannel,
+};
 
 mod renderer;
 use self::renderer::AsciiRenderer;
@@ -10,9 +13,11 @@ const MAP_HEIGHT: u32 = 10;
 
 use core::Point3;
 use libdwarf::{
-    resources::{Map, TaskQueue},
+    components::Labor,
+    events::SimEvent,
+    resources::{JobBoard, Map, TaskQueue},
     systems,
-    trigger::TriggerType,
+    trigger::{TriggerPriority, TriggerType},
     world::WorldSim,
 };
 use libterrain::TerrainChunk;
@@ -26,7 +31,10 @@ fn main() {
 
     // Initialize the world.
     let terrain = TerrainChunk::new(MAP_WIDTH, MAP_HEIGHT);
-    WorldSim::new(&mut world, &terrain, MAP_WIDTH, MAP_HEIGHT);
+    WorldSim::builder()
+        .with_data_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/../resources"))
+        .build(&mut world, &terrain, MAP_WIDTH, MAP_HEIGHT)
+        .expect("Failed to initialize world");
 
     let mut dispatcher = DispatcherBuilder::new()
         .with(systems::WorkerSystem, "worker_sim", &[])
@@ -40,6 +48,10 @@ fn main() {
         .build();
 
     dispatcher.setup(&mut world);
+    // Listen in on what's happening in the simulation.
+    let mut events = world
+        .write_resource::<EventChannel<SimEvent>>()
+        .register_reader();
     // Add entities to the world
     world.exec(|(mut queue,): (ecs::Write<TaskQueue>,)| {
         queue.add_world(TriggerType::AddWorker(Point3::new(0, 0, 0)));
@@ -52,16 +64,20 @@ fn main() {
         renderer.render(&world);
 
         match input.read_char().unwrap() {
-            // Add a task to the task queue.
+            // Post a job to the job board.
             'a' => {
                 world.exec(
-                    |(mut queue, map): (ecs::Write<TaskQueue>, ecs::ReadExpect<Map>)| {
-                        let entity_id = map.object_map.get(&Point3::new(9, 9, 0)).unwrap();
-                        queue.add(TriggerType::HarvestResource {
-                            target: *entity_id,
-                            position: Point3::new(9, 9, 0),
-                            resource: String::from("wood"),
-                        });
+                    |(mut board, map): (ecs::Write<JobBoard>, ecs::ReadExpect<Map>)| {
+                        let entity_id = map.objects_at(Point3::new(9, 9, 0)).unwrap();
+                        board.post(
+                            TriggerType::HarvestResource {
+                                target: entity_id,
+                                position: Point3::new(9, 9, 0),
+                                resource: String::from("wood"),
+                            },
+                            TriggerPriority::MEDIUM,
+                            Some(Labor::Gathering),
+                        );
                     },
                 );
             }
@@ -73,6 +89,13 @@ fn main() {
                 // Tick map
                 dispatcher.dispatch(&mut world);
                 world.maintain();
+
+                for event in world
+                    .read_resource::<EventChannel<SimEvent>>()
+                    .read(&mut events)
+                {
+                    println!("{:?}", event);
+                }
             }
             _ => {}
         }
@@ -3,7 +3,7 @@ use crossterm::{cursor, terminal, ClearType, Terminal, TerminalCursor};
 
 use core::Point3;
 use libdwarf::{
-    components::{EntityInfo, MapObject, Worker},
+    components::{EntityInfo, Inventory, MapObject, Worker},
     resources::Map,
 };
 
@@ -93,7 +93,8 @@ impl AsciiRenderer {
         print!("\n\rWorkers\n\r--------------\n\r");
         let entities = world.entities();
         let workers = world.read_storage::<Worker>();
-        for (entity, worker) in (&entities, &workers).join() {
+        let inventories = world.read_storage::<Inventory>();
+        for (entity, worker, inventory) in (&entities, &workers, &inventories).join() {
             print!("[W{}: Current Action]\n\r", entity.id());
             print!("- {:?}\n\r", worker.current_action);
 
@@ -101,8 +102,8 @@ impl AsciiRenderer {
             print!("- {:?}\n\r", worker.current_path);
 
             print!("[W{}: Inventory]\n\r", entity.id());
-            for obj in worker.inventory.iter() {
-                print!("- {:?}\n\r", obj.to_string());
+            for stack in inventory.stacks.iter() {
+                print!("- {}\n\r", stack);
             }
 
             print!("[W{}: Task Queue]\n\r", entity.id());
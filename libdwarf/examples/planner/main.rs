@@ -1,6 +1,4 @@
 use libdwarf::planner::{Condition, Planner, State};
-use ron::de::from_reader;
-use std::fs::File;
 
 fn main() {
     let input_path = format!(
@@ -9,9 +7,7 @@ fn main() {
     );
     println!("{}", input_path);
 
-    let f = File::open(&input_path).expect("Failed opening file");
-
-    let planner: Planner = match from_reader(f) {
+    let planner = match Planner::load(&input_path) {
         Ok(x) => x,
         Err(e) => {
             println!("Failed to load config: {}", e);
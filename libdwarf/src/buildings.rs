@@ -0,0 +1,98 @@
+///
+/// Blueprints for things workers can build, e.g. walls & beds.
+///
+/// Placing a blueprint on the map creates a construction site. Workers haul
+/// the required materials over to the site & then put in the work until it's
+/// done, at which point the site is replaced w/ whatever the blueprint builds.
+///
+use core::Point3;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::{config::read_ron, error::Error};
+
+/// What a finished construction site turns into.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub enum Construct {
+    /// Fill in the footprint w/ solid blocks, <height> blocks high.
+    Block { height: u32 },
+    /// Spawn an object of this resource type, e.g. "bed".
+    Object(String),
+}
+
+fn default_footprint() -> (u32, u32) {
+    (1, 1)
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Blueprint {
+    /// Display name, e.g. "wall".
+    pub name: String,
+    /// Number of tiles covered along the x & y axis, starting from where
+    /// the blueprint is placed.
+    #[serde(default = "default_footprint")]
+    pub footprint: (u32, u32),
+    /// Resources that need to be delivered before work can start.
+    #[serde(default)]
+    pub materials: HashMap<String, u32>,
+    /// Amount of work needed to finish once the materials are in.
+    pub work: u32,
+    pub result: Construct,
+}
+
+impl Blueprint {
+    /// Tiles covered when placed at <origin>.
+    pub fn tiles(&self, origin: &Point3<u32>) -> Vec<Point3<u32>> {
+        let (width, depth) = self.footprint;
+        (origin.y..origin.y + depth)
+            .flat_map(|y| (origin.x..origin.x + width).map(move |x| Point3::new(x, y, origin.z)))
+            .collect()
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct BlueprintLibrary {
+    blueprints: HashMap<String, Blueprint>,
+}
+
+impl BlueprintLibrary {
+    pub fn load<P: AsRef<Path>>(input_path: P) -> Result<Self, Error> {
+        read_ron(input_path.as_ref())
+    }
+
+    pub fn get(&self, name: &str) -> Result<&Blueprint, Error> {
+        self.blueprints
+            .get(name)
+            .ok_or_else(|| Error::UnknownBlueprint(name.to_string()))
+    }
+
+    /// Ids of every blueprint, sorted by name.
+    pub fn blueprint_ids(&self) -> Vec<&String> {
+        let mut ids: Vec<&String> = self.blueprints.keys().collect();
+        ids.sort();
+        ids
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BlueprintLibrary, Construct};
+    use core::Point3;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_load_blueprints() {
+        let path =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../resources/data/buildings.ron");
+        let library = BlueprintLibrary::load(path).unwrap();
+
+        let wall = library.get("wall").unwrap();
+        assert_eq!(wall.result, Construct::Block { height: 2 });
+        assert_eq!(
+            wall.tiles(&Point3::new(2, 2, 0)),
+            vec![Point3::new(2, 2, 0)]
+        );
+        assert!(library.get("castle").is_err());
+    }
+}
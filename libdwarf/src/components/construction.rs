@@ -0,0 +1,104 @@
+use core::amethyst::ecs::{Component, VecStorage};
+use std::collections::HashMap;
+
+use crate::buildings::Blueprint;
+
+/// Blueprint placed on the map, waiting on materials & work to be finished.
+/// Delivered materials & progress stick around if the workers on it wander
+/// off, so anyone can pick up where they left off.
+#[derive(Clone, Component, Debug)]
+#[storage(VecStorage)]
+pub struct Construction {
+    pub blueprint: Blueprint,
+    /// Materials delivered so far.
+    pub delivered: HashMap<String, u32>,
+    /// Amount of work put in so far.
+    pub progress: u32,
+}
+
+impl Construction {
+    pub fn new(blueprint: &Blueprint) -> Self {
+        Construction {
+            blueprint: blueprint.clone(),
+            delivered: HashMap::new(),
+            progress: 0,
+        }
+    }
+
+    /// Number of <resource> still needed before work can start.
+    pub fn needs(&self, resource: &str) -> u32 {
+        let required = self.blueprint.materials.get(resource).copied().unwrap_or(0);
+        let delivered = self.delivered.get(resource).copied().unwrap_or(0);
+        required.saturating_sub(delivered)
+    }
+
+    /// Materials still needed & how many of each.
+    pub fn missing(&self) -> Vec<(&String, u32)> {
+        self.blueprint
+            .materials
+            .keys()
+            .map(|resource| (resource, self.needs(resource)))
+            .filter(|(_, count)| *count > 0)
+            .collect()
+    }
+
+    pub fn has_materials(&self) -> bool {
+        self.missing().is_empty()
+    }
+
+    /// Hand over up to <count> of <resource>. Returns how many were used,
+    /// anything beyond what the blueprint needs is turned away.
+    pub fn deliver(&mut self, resource: &str, count: u32) -> u32 {
+        let used = count.min(self.needs(resource));
+        if used > 0 {
+            *self.delivered.entry(resource.to_string()).or_insert(0) += used;
+        }
+
+        used
+    }
+
+    /// Put in <amount> of work. Nothing happens until all the materials are in.
+    pub fn work(&mut self, amount: u32) {
+        if self.has_materials() {
+            self.progress = (self.progress + amount).min(self.blueprint.work);
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.has_materials() && self.progress >= self.blueprint.work
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Construction;
+    use crate::buildings::{Blueprint, Construct};
+
+    #[test]
+    fn test_construction() {
+        let blueprint = Blueprint {
+            name: "wall".to_string(),
+            footprint: (1, 1),
+            materials: vec![("wood".to_string(), 2)].into_iter().collect(),
+            work: 3,
+            result: Construct::Block { height: 2 },
+        };
+
+        let mut site = Construction::new(&blueprint);
+        // No progress until the materials are in.
+        site.work(1);
+        assert_eq!(site.progress, 0);
+
+        assert_eq!(site.deliver("wood", 1), 1);
+        assert_eq!(site.needs("wood"), 1);
+        assert_eq!(site.deliver("wood", 5), 1);
+        assert_eq!(site.deliver("stone", 1), 0);
+        assert!(site.has_materials());
+
+        site.work(2);
+        assert!(!site.is_done());
+        site.work(2);
+        assert!(site.is_done());
+        assert_eq!(site.progress, 3);
+    }
+}
@@ -0,0 +1,60 @@
+use core::amethyst::ecs::{Component, VecStorage};
+use libterrain::Path;
+
+use crate::{components::Health, creatures::CreatureType};
+
+/// Seconds between each attack a creature makes.
+const ATTACK_COOLDOWN: f32 = 1.0;
+
+/// Creature roaming the map, see `CreatureLibrary`.
+#[derive(Clone, Component, Debug)]
+#[storage(VecStorage)]
+pub struct Creature {
+    pub creature_type: CreatureType,
+    pub health: Health,
+    /// Worker it's going after, or running from in the case of wildlife.
+    pub target: Option<u32>,
+    /// Steps left to get to the target, stored in reverse like the worker's.
+    pub path: Path,
+    /// Fraction of a step built up so far, creatures take a step for each
+    /// whole one.
+    pub stride: f32,
+    /// Seconds until it can attack again.
+    pub cooldown: f32,
+}
+
+impl Creature {
+    pub fn new(creature_type: &CreatureType) -> Self {
+        Creature {
+            creature_type: creature_type.clone(),
+            health: Health::new(creature_type.health as f32),
+            target: None,
+            path: Vec::new(),
+            stride: 0.0,
+            cooldown: 0.0,
+        }
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.health.is_dead()
+    }
+
+    /// Wait out the attack cooldown. Returns true if it's ready to attack.
+    pub fn ready_to_attack(&mut self, delta_seconds: f32) -> bool {
+        self.cooldown = (self.cooldown - delta_seconds).max(0.0);
+        self.cooldown <= 0.0
+    }
+
+    pub fn attacked(&mut self) {
+        self.cooldown = ATTACK_COOLDOWN;
+    }
+
+    /// Build up movement over <delta_seconds>. Returns the number of steps
+    /// it gets to take.
+    pub fn steps(&mut self, delta_seconds: f32) -> u32 {
+        self.stride += self.creature_type.speed * delta_seconds;
+        let steps = self.stride.floor();
+        self.stride -= steps;
+        steps as u32
+    }
+}
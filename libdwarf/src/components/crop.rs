@@ -0,0 +1,80 @@
+use core::amethyst::ecs::{Component, VecStorage};
+
+use crate::crops::CropType;
+
+/// Crop planted on a farm plot, growing until it's ready to harvest.
+#[derive(Clone, Component, Debug)]
+#[storage(VecStorage)]
+pub struct Crop {
+    pub crop_type: CropType,
+    /// Number of ticks spent growing so far.
+    pub growth: u32,
+}
+
+impl Crop {
+    pub fn new(crop_type: &CropType) -> Self {
+        Crop {
+            crop_type: crop_type.clone(),
+            growth: 0,
+        }
+    }
+
+    /// Grow for another tick. Returns true if the crop moved on to its next
+    /// growth stage.
+    pub fn grow(&mut self) -> bool {
+        if self.is_grown() {
+            return false;
+        }
+
+        let stage = self.stage();
+        self.growth += 1;
+        self.stage() != stage
+    }
+
+    pub fn stage(&self) -> usize {
+        self.crop_type.stage(self.growth)
+    }
+
+    /// Sprite idx for the current growth stage.
+    pub fn sprite(&self) -> usize {
+        self.crop_type.sprite(self.growth)
+    }
+
+    pub fn is_grown(&self) -> bool {
+        self.growth >= self.crop_type.growth
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Crop;
+    use crate::{crops::CropType, resources::Season};
+    use libterrain::Biome;
+
+    #[test]
+    fn test_crop_growth() {
+        let crop_type = CropType {
+            name: "wheat".to_string(),
+            stages: vec![5, 6, 7],
+            growth: 4,
+            biomes: vec![Biome::GRASSLAND],
+            seasons: vec![Season::Spring],
+            yields: vec![("wheat".to_string(), 2)].into_iter().collect(),
+        };
+
+        let mut crop = Crop::new(&crop_type);
+        assert_eq!(crop.sprite(), 5);
+        assert!(!crop.grow());
+        assert!(crop.grow());
+        assert_eq!(crop.sprite(), 6);
+        assert!(!crop.grow());
+        assert!(!crop.is_grown());
+
+        // Only ripe once fully grown.
+        assert!(crop.grow());
+        assert!(crop.is_grown());
+        assert_eq!(crop.sprite(), 7);
+        assert!(!crop.grow());
+        assert_eq!(crop.growth, 4);
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{
+    components::ToolClass,
+    planner::{Condition, State},
+};
+
+/// Places a worker can equip something.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Slot {
+    MainHand,
+    OffHand,
+}
+
+impl Slot {
+    pub const ALL: [Slot; 2] = [Slot::MainHand, Slot::OffHand];
+}
+
+/// A tool that's been equipped by a worker, see `ResourceAttribute::Tool`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tool {
+    /// Resource type this was made from, e.g. "axe".
+    pub resource: String,
+    pub class: ToolClass,
+    pub chopping: u32,
+    pub digging: u32,
+    /// Uses left before it breaks.
+    pub durability: u32,
+}
+
+impl Tool {
+    /// Extra damage dealt when used for work that needs a <class> tool.
+    pub fn power(&self, class: ToolClass) -> u32 {
+        match class {
+            ToolClass::Axe => self.chopping,
+            ToolClass::Pickaxe => self.digging,
+        }
+    }
+
+    /// Use this tool once. Returns true if it broke.
+    pub fn wear(&mut self) -> bool {
+        self.durability = self.durability.saturating_sub(1);
+        self.durability == 0
+    }
+}
+
+impl fmt::Display for Tool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({} uses left)", self.resource, self.durability)
+    }
+}
+
+/// Tools a worker has equipped. Tools stay equipped until they break.
+#[derive(Clone, Debug, Default)]
+pub struct Equipment {
+    slots: HashMap<Slot, Tool>,
+}
+
+impl Equipment {
+    /// Equip <tool> in the first free slot. If every slot is taken, it
+    /// replaces the off hand. Returns whatever was replaced.
+    pub fn equip(&mut self, tool: Tool) -> Option<Tool> {
+        let slot = Slot::ALL
+            .iter()
+            .find(|slot| !self.slots.contains_key(slot))
+            .copied()
+            .unwrap_or(Slot::OffHand);
+
+        self.slots.insert(slot, tool)
+    }
+
+    pub fn get(&self, slot: Slot) -> Option<&Tool> {
+        self.slots.get(&slot)
+    }
+
+    /// Equipped tool of <class>, if any.
+    pub fn tool(&self, class: ToolClass) -> Option<&Tool> {
+        Slot::ALL
+            .iter()
+            .filter_map(|slot| self.slots.get(slot))
+            .find(|tool| tool.class == class)
+    }
+
+    pub fn has(&self, class: ToolClass) -> bool {
+        self.tool(class).is_some()
+    }
+
+    /// Extra damage from swinging the best tool the worker has equipped.
+    pub fn weapon_power(&self) -> u32 {
+        self.slots
+            .values()
+            .map(|tool| tool.chopping.max(tool.digging))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Use the equipped <class> tool once. Returns the tool if it broke,
+    /// it's no longer equipped.
+    pub fn wear(&mut self, class: ToolClass) -> Option<Tool> {
+        let slot = Slot::ALL
+            .iter()
+            .find(|slot| {
+                self.slots
+                    .get(slot)
+                    .map(|tool| tool.class == class)
+                    .unwrap_or(false)
+            })
+            .copied()?;
+
+        let broke = self.slots.get_mut(&slot).map(Tool::wear).unwrap_or(false);
+        if broke {
+            self.slots.remove(&slot)
+        } else {
+            None
+        }
+    }
+
+    /// Add the equipped tools to the planner state.
+    pub fn add_state(&self, state: &mut State) {
+        for tool in self.slots.values() {
+            state.insert(Condition::Equipped(tool.class.name().to_string()), true);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Equipment, Slot, Tool};
+    use crate::components::ToolClass;
+
+    fn axe(durability: u32) -> Tool {
+        Tool {
+            resource: "axe".to_string(),
+            class: ToolClass::Axe,
+            chopping: 10,
+            digging: 0,
+            durability,
+        }
+    }
+
+    #[test]
+    fn test_equipment() {
+        let mut equipment = Equipment::default();
+        assert!(equipment.equip(axe(2)).is_none());
+        assert!(equipment.has(ToolClass::Axe));
+        assert!(!equipment.has(ToolClass::Pickaxe));
+        assert_eq!(
+            equipment
+                .tool(ToolClass::Axe)
+                .unwrap()
+                .power(ToolClass::Axe),
+            10
+        );
+
+        // Off hand gets swapped out once both hands are full.
+        assert!(equipment.equip(axe(5)).is_none());
+        assert_eq!(equipment.equip(axe(7)).unwrap().durability, 5);
+        assert_eq!(equipment.get(Slot::OffHand).unwrap().durability, 7);
+
+        // Tools break once they're worn out.
+        assert!(equipment.wear(ToolClass::Axe).is_none());
+        assert_eq!(equipment.wear(ToolClass::Axe).unwrap().durability, 0);
+        assert!(equipment.get(Slot::MainHand).is_none());
+        assert!(equipment.has(ToolClass::Axe));
+    }
+}
@@ -0,0 +1,95 @@
+use crate::config::HealthConfig;
+
+/// Hit points & open wounds of a worker or creature. Wounds bleed for a
+/// while after each hit & only start healing once they've stopped.
+#[derive(Clone, Debug)]
+pub struct Health {
+    pub hp: f32,
+    pub max: f32,
+    /// Hit points lost per second to open wounds.
+    pub bleeding: f32,
+    /// Whoever landed the last hit, credited w/ the kill if it bleeds out.
+    pub last_attacker: Option<u32>,
+}
+
+impl Health {
+    pub fn new(max: f32) -> Self {
+        Health {
+            hp: max,
+            max,
+            bleeding: 0.0,
+            last_attacker: None,
+        }
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.hp <= 0.0
+    }
+
+    /// How much health is left, from 0.0 to 1.0.
+    pub fn fraction(&self) -> f32 {
+        if self.max <= 0.0 {
+            return 0.0;
+        }
+
+        (self.hp / self.max).clamp(0.0, 1.0)
+    }
+
+    /// Take a hit of <damage> from <attacker>, opening up a wound.
+    pub fn wound(&mut self, damage: i32, attacker: u32, config: &HealthConfig) {
+        self.hp -= damage as f32;
+        self.bleeding += damage as f32 * config.bleed_rate;
+        self.last_attacker = Some(attacker);
+    }
+
+    /// Bleed out or heal up over <delta_seconds>.
+    pub fn update(&mut self, delta_seconds: f32, config: &HealthConfig) {
+        if self.is_dead() {
+            return;
+        }
+
+        if self.bleeding > 0.0 {
+            self.hp -= self.bleeding * delta_seconds;
+            self.bleeding = (self.bleeding - config.clot_rate * delta_seconds).max(0.0);
+        } else {
+            self.hp = (self.hp + config.heal_rate * delta_seconds).min(self.max);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Health;
+    use crate::config::HealthConfig;
+
+    #[test]
+    fn test_health() {
+        let config = HealthConfig {
+            worker_health: 100.0,
+            bleed_rate: 0.1,
+            clot_rate: 0.5,
+            heal_rate: 1.0,
+        };
+
+        let mut health = Health::new(100.0);
+        health.wound(20, 7, &config);
+        assert_eq!(health.hp, 80.0);
+        assert_eq!(health.bleeding, 2.0);
+        assert_eq!(health.last_attacker, Some(7));
+
+        // Keeps bleeding for a bit, then heals back up.
+        health.update(1.0, &config);
+        assert_eq!(health.hp, 78.0);
+        for _ in 0..4 {
+            health.update(1.0, &config);
+        }
+        assert_eq!(health.bleeding, 0.0);
+        let wounded = health.hp;
+        health.update(1.0, &config);
+        assert_eq!(health.hp, wounded + 1.0);
+
+        health.wound(100, 8, &config);
+        assert!(health.is_dead());
+        assert_eq!(health.fraction(), 0.0);
+    }
+}
@@ -0,0 +1,230 @@
+use core::amethyst::ecs::{Component, VecStorage};
+use serde::Deserialize;
+use std::fmt;
+
+use crate::{
+    components::MapObject,
+    planner::{Condition, State},
+};
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Quality {
+    Poor,
+    Normal,
+    Fine,
+    Masterwork,
+}
+
+/// A number of identical items. Items only stack if they're made of the same
+/// material & are of the same quality.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ItemStack {
+    /// Resource type, e.g. "wood".
+    pub resource: String,
+    pub count: u32,
+    /// What the item is made out of, if it matters, e.g. "oak".
+    pub material: Option<String>,
+    pub quality: Quality,
+    /// Weight of a single item.
+    pub weight: u32,
+}
+
+impl ItemStack {
+    pub fn new(resource: &str, count: u32, weight: u32) -> Self {
+        ItemStack {
+            resource: resource.to_string(),
+            count,
+            material: None,
+            quality: Quality::Normal,
+            weight,
+        }
+    }
+
+    /// A single item, picked up off the map.
+    pub fn from_object(object: &MapObject) -> Self {
+        ItemStack {
+            resource: object.resource_type.name.clone(),
+            count: 1,
+            material: object.material.clone(),
+            quality: object.quality,
+            weight: object.resource_type.weight,
+        }
+    }
+
+    pub fn can_stack(&self, other: &ItemStack) -> bool {
+        self.resource == other.resource
+            && self.material == other.material
+            && self.quality == other.quality
+    }
+
+    pub fn total_weight(&self) -> u32 {
+        self.count * self.weight
+    }
+}
+
+impl fmt::Display for ItemStack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} x{}", self.resource, self.count)?;
+        if let Some(material) = &self.material {
+            write!(f, " ({})", material)?;
+        }
+        if self.quality != Quality::Normal {
+            write!(f, " [{:?}]", self.quality)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Items being carried around by a worker.
+#[derive(Clone, Component, Debug, Default)]
+#[storage(VecStorage)]
+pub struct Inventory {
+    pub stacks: Vec<ItemStack>,
+    /// Max total weight that can be carried.
+    pub max_weight: u32,
+}
+
+impl Inventory {
+    pub fn new(max_weight: u32) -> Self {
+        Inventory {
+            stacks: Vec::new(),
+            max_weight,
+        }
+    }
+
+    pub fn weight(&self) -> u32 {
+        self.stacks.iter().map(|stack| stack.total_weight()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stacks.is_empty()
+    }
+
+    /// Number of <resource> being carried, regardless of material/quality.
+    pub fn count(&self, resource: &str) -> u32 {
+        self.stacks
+            .iter()
+            .filter(|stack| stack.resource == resource)
+            .map(|stack| stack.count)
+            .sum()
+    }
+
+    pub fn has(&self, resource: &str) -> bool {
+        self.count(resource) > 0
+    }
+
+    /// Add as much of <item> as we can carry. Returns whatever didn't fit.
+    pub fn add(&mut self, mut item: ItemStack) -> Option<ItemStack> {
+        let room = self.max_weight.saturating_sub(self.weight());
+        // Weightless items always fit.
+        let fits = match room.checked_div(item.weight) {
+            Some(max) => item.count.min(max),
+            None => item.count,
+        };
+
+        if fits > 0 {
+            match self.stacks.iter_mut().find(|stack| stack.can_stack(&item)) {
+                Some(stack) => stack.count += fits,
+                None => self.stacks.push(ItemStack {
+                    count: fits,
+                    ..item.clone()
+                }),
+            }
+        }
+
+        item.count -= fits;
+        if item.count > 0 {
+            Some(item)
+        } else {
+            None
+        }
+    }
+
+    /// Take out up to <count> of <resource>, starting w/ the first stack
+    /// picked up. Returns `None` if there's none to take.
+    pub fn remove(&mut self, resource: &str, count: u32) -> Option<ItemStack> {
+        let idx = self
+            .stacks
+            .iter()
+            .position(|stack| stack.resource == resource)?;
+
+        let stack = &mut self.stacks[idx];
+        let taken = stack.count.min(count);
+        stack.count -= taken;
+
+        let removed = ItemStack {
+            count: taken,
+            ..stack.clone()
+        };
+        if stack.count == 0 {
+            self.stacks.remove(idx);
+        }
+
+        Some(removed)
+    }
+
+    /// Move up to <count> of <resource> into <other>. Anything that doesn't
+    /// fit stays put. Returns the number of items moved.
+    pub fn transfer(&mut self, other: &mut Inventory, resource: &str, count: u32) -> u32 {
+        let mut moved = 0;
+        while moved < count {
+            let item = match self.remove(resource, count - moved) {
+                Some(item) => item,
+                None => break,
+            };
+
+            let taken = item.count;
+            match other.add(item) {
+                Some(leftover) => {
+                    moved += taken - leftover.count;
+                    self.add(leftover);
+                    break;
+                }
+                None => moved += taken,
+            }
+        }
+
+        moved
+    }
+
+    /// Add what the worker is carrying to the planner state.
+    pub fn add_state(&self, state: &mut State) {
+        for stack in self.stacks.iter() {
+            state.insert(Condition::Has(stack.resource.clone()), true);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Inventory, ItemStack, Quality};
+
+    #[test]
+    fn test_inventory() {
+        let mut inventory = Inventory::new(10);
+        assert!(inventory.add(ItemStack::new("wood", 3, 2)).is_none());
+        // Only room for 2 more wood.
+        let leftover = inventory.add(ItemStack::new("wood", 3, 2)).unwrap();
+        assert_eq!(leftover.count, 1);
+        assert_eq!(inventory.count("wood"), 5);
+        assert_eq!(inventory.stacks.len(), 1);
+        assert_eq!(inventory.weight(), 10);
+
+        let mut fine = Inventory::new(10);
+        fine.add(ItemStack {
+            quality: Quality::Fine,
+            ..ItemStack::new("wood", 1, 2)
+        });
+        // Fine wood doesn't stack w/ regular wood.
+        assert_eq!(fine.transfer(&mut inventory, "wood", 1), 0);
+        assert_eq!(inventory.transfer(&mut fine, "wood", 10), 4);
+        assert_eq!(fine.stacks.len(), 2);
+        assert_eq!(inventory.count("wood"), 1);
+
+        let removed = inventory.remove("wood", 5).unwrap();
+        assert_eq!(removed.count, 1);
+        assert!(inventory.is_empty());
+        assert!(inventory.remove("wood", 1).is_none());
+    }
+}
@@ -3,12 +3,28 @@ use core::{
     Point3,
 };
 
+mod construction;
+mod creature;
+mod crop;
+mod equipment;
+mod health;
+mod inventory;
+mod needs;
 mod object;
 mod resource;
+mod skills;
 mod worker;
 
+pub use construction::*;
+pub use creature::*;
+pub use crop::*;
+pub use equipment::*;
+pub use health::*;
+pub use inventory::*;
+pub use needs::*;
 pub use object::*;
 pub use resource::*;
+pub use skills::*;
 pub use worker::*;
 
 #[derive(Clone, Component, Debug)]
@@ -0,0 +1,177 @@
+use std::fmt;
+
+use crate::config::NeedsConfig;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Need {
+    Hunger,
+    Thirst,
+    Rest,
+    Social,
+}
+
+impl Need {
+    pub const ALL: [Need; 4] = [Need::Hunger, Need::Thirst, Need::Rest, Need::Social];
+    /// Needs workers can't go w/o, they'll stop working if these are left
+    /// unmet for too long.
+    pub const PHYSICAL: [Need; 3] = [Need::Hunger, Need::Thirst, Need::Rest];
+
+    /// Name used to refer to this need in planner conditions, e.g.
+    /// `Satisfied("hunger")`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Need::Hunger => "hunger",
+            Need::Thirst => "thirst",
+            Need::Rest => "rest",
+            Need::Social => "social",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Need> {
+        Need::ALL.iter().find(|need| need.name() == name).copied()
+    }
+
+    /// What workers go looking for to satisfy this need, used in planner
+    /// conditions, e.g. `Near("bed")`.
+    pub fn target(self) -> &'static str {
+        match self {
+            Need::Hunger => "food",
+            Need::Thirst => "water",
+            Need::Rest => "bed",
+            Need::Social => "worker",
+        }
+    }
+}
+
+impl fmt::Display for Need {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Tracks how satisfied a worker is. Each need ranges from 0.0 (desperate)
+/// to 1.0 (fully satisfied) and slowly decays over time.
+#[derive(Clone, Debug)]
+pub struct Needs {
+    pub hunger: f32,
+    pub thirst: f32,
+    pub rest: f32,
+    pub social: f32,
+}
+
+impl Default for Needs {
+    fn default() -> Self {
+        Needs {
+            hunger: 1.0,
+            thirst: 1.0,
+            rest: 1.0,
+            social: 1.0,
+        }
+    }
+}
+
+impl Needs {
+    pub fn get(&self, need: Need) -> f32 {
+        match need {
+            Need::Hunger => self.hunger,
+            Need::Thirst => self.thirst,
+            Need::Rest => self.rest,
+            Need::Social => self.social,
+        }
+    }
+
+    fn get_mut(&mut self, need: Need) -> &mut f32 {
+        match need {
+            Need::Hunger => &mut self.hunger,
+            Need::Thirst => &mut self.thirst,
+            Need::Rest => &mut self.rest,
+            Need::Social => &mut self.social,
+        }
+    }
+
+    pub fn is_satisfied(&self, need: Need) -> bool {
+        self.get(need) >= 1.0
+    }
+
+    /// Decay needs based on the amount of time that has passed.
+    pub fn decay(&mut self, delta_seconds: f32, config: &NeedsConfig) {
+        for need in Need::ALL.iter() {
+            let decay = config.rates(*need).decay * delta_seconds;
+            let value = self.get_mut(*need);
+            *value = (*value - decay).max(0.0);
+        }
+    }
+
+    /// Use up <amount> of a need on top of the usual decay, e.g. from being
+    /// caught out in the cold.
+    pub fn drain(&mut self, need: Need, amount: f32) {
+        let value = self.get_mut(need);
+        *value = (*value - amount).max(0.0);
+    }
+
+    /// Restore a need by <amount> of what a single action usually recovers,
+    /// e.g. 0.5 for sleeping on the floor.
+    pub fn restore(&mut self, need: Need, amount: f32, config: &NeedsConfig) {
+        let recovery = config.rates(need).recovery * amount;
+        let value = self.get_mut(need);
+        *value = (*value + recovery).min(1.0);
+    }
+
+    /// Physical need that's closest to running out.
+    fn lowest(&self) -> f32 {
+        Need::PHYSICAL
+            .iter()
+            .map(|need| self.get(*need))
+            .fold(1.0, f32::min)
+    }
+
+    /// Is this need low enough to drop what we're doing?
+    pub fn is_urgent(&self, need: Need, config: &NeedsConfig) -> bool {
+        Need::PHYSICAL.contains(&need) && self.get(need) < config.seek_threshold
+    }
+
+    /// How fast the worker regains energy, slowing down once their needs
+    /// have been left unmet for a while.
+    pub fn work_rate(&self, config: &NeedsConfig) -> f32 {
+        if self.lowest() < config.slow_threshold {
+            config.slow_factor
+        } else {
+            1.0
+        }
+    }
+
+    /// Too hungry, thirsty or tired to do anything but take care of it.
+    pub fn is_desperate(&self, config: &NeedsConfig) -> bool {
+        self.lowest() < config.stop_threshold
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Need, Needs};
+    use crate::config::NeedsConfig;
+
+    #[test]
+    fn test_unmet_needs() {
+        let config = NeedsConfig::default();
+        let mut needs = Needs::default();
+        assert_eq!(needs.work_rate(&config), 1.0);
+
+        // Thirst runs out before hunger does.
+        needs.decay(210.0, &config);
+        assert!(needs.thirst < needs.hunger);
+        assert!(needs.is_urgent(Need::Thirst, &config));
+        assert!(!needs.is_urgent(Need::Rest, &config));
+        assert_eq!(needs.work_rate(&config), config.slow_factor);
+        assert!(!needs.is_desperate(&config));
+
+        needs.decay(100.0, &config);
+        assert_eq!(needs.thirst, 0.0);
+        assert!(needs.is_desperate(&config));
+
+        needs.restore(Need::Thirst, 1.0, &config);
+        needs.restore(Need::Hunger, 1.0, &config);
+        assert!(!needs.is_desperate(&config));
+        assert!(!needs.is_urgent(Need::Thirst, &config));
+    }
+}
@@ -1,12 +1,21 @@
 use core::amethyst::ecs::{Component, VecStorage};
 
-use crate::components::{ResourceAttribute, ResourceType};
+use crate::{
+    components::{Quality, ResourceAttribute, ResourceType},
+    resources::SimRng,
+};
 
 #[derive(Clone, Component, Debug)]
 #[storage(VecStorage)]
 pub struct MapObject {
     pub health: i32,
+    /// Set once this object has been queued up for destruction so it only
+    /// happens once.
+    pub destroy_queued: bool,
     pub resource_type: ResourceType,
+    /// What this object is made out of, if it matters.
+    pub material: Option<String>,
+    pub quality: Quality,
 }
 
 impl MapObject {
@@ -25,6 +34,9 @@ impl MapObject {
         MapObject {
             resource_type: resource_type.clone(),
             health: default_health,
+            destroy_queued: false,
+            material: None,
+            quality: Quality::Normal,
         }
     }
 
@@ -32,6 +44,32 @@ impl MapObject {
         self.health <= 0
     }
 
+    /// Items are indestructible objects that can be picked up & carried
+    /// around, e.g. wood.
+    pub fn is_item(&self) -> bool {
+        !self
+            .resource_type
+            .attributes
+            .iter()
+            .any(|x| matches!(x, ResourceAttribute::Health(_)))
+    }
+
+    /// Can workers make things here?
+    pub fn is_workshop(&self) -> bool {
+        self.resource_type
+            .attributes
+            .iter()
+            .any(|x| matches!(x, ResourceAttribute::Workshop))
+    }
+
+    /// Does destroying this object drop <resource>?
+    pub fn drops(&self, resource: &str) -> bool {
+        self.drop_table().iter().any(|x| match x {
+            ResourceAttribute::Drops { resource: name, .. } => name == resource,
+            _ => false,
+        })
+    }
+
     pub fn drop_table(&self) -> Vec<&ResourceAttribute> {
         self.resource_type
             .attributes
@@ -40,6 +78,31 @@ impl MapObject {
             .collect()
     }
 
+    /// Roll the drop table, returning how many of each resource to drop.
+    pub fn roll_drops(&self, rng: &mut SimRng) -> Vec<(String, u32)> {
+        let mut drops = Vec::new();
+        for drop in self.drop_table() {
+            if let ResourceAttribute::Drops {
+                resource,
+                min,
+                max,
+                chance,
+            } = drop
+            {
+                if !rng.chance(*chance) {
+                    continue;
+                }
+
+                let count = rng.range(*min, *max);
+                if count > 0 {
+                    drops.push((resource.clone(), count));
+                }
+            }
+        }
+
+        drops
+    }
+
     pub fn to_string(&self) -> String {
         format!("{} ({})", self.resource_type.name, self.health)
     }
@@ -1,4 +1,6 @@
 use serde::Deserialize;
+
+use crate::components::Tool;
 ///
 /// Defines a generic resource type. These act as constants that will be used by
 /// the actual objects to determine their behavior.
@@ -13,42 +15,170 @@ use serde::Deserialize;
 #[derive(Clone, Debug, Deserialize)]
 pub enum ResourceAttribute {
     Health(u32),
-    Drops(String, u32),
+    /// Drop between <min> & <max> of <resource> when destroyed. <chance> is
+    /// how likely (0.0 - 1.0) it is to drop anything at all.
+    Drops {
+        resource: String,
+        #[serde(default = "default_count")]
+        min: u32,
+        #[serde(default = "default_count")]
+        max: u32,
+        #[serde(default = "default_chance")]
+        chance: f32,
+    },
+    /// Workers can make things here, see `RecipeBook`.
+    Workshop,
+    /// Can be equipped & used as a tool, see `Equipment`.
+    Tool {
+        class: ToolClass,
+        /// Extra damage dealt when chopping.
+        #[serde(default)]
+        chopping: u32,
+        /// Extra damage dealt when digging.
+        #[serde(default)]
+        digging: u32,
+        /// Number of uses before it breaks.
+        durability: u32,
+    },
+}
+
+/// Kinds of tools. Actions can require one to be equipped, see
+/// `Action::tool`.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+pub enum ToolClass {
+    Axe,
+    Pickaxe,
+}
+
+impl ToolClass {
+    pub const ALL: [ToolClass; 2] = [ToolClass::Axe, ToolClass::Pickaxe];
+
+    /// Name used to refer to this class in planner conditions, e.g.
+    /// `Equipped("axe")`.
+    pub fn name(self) -> &'static str {
+        match self {
+            ToolClass::Axe => "axe",
+            ToolClass::Pickaxe => "pickaxe",
+        }
+    }
+
+    /// Class called <name> in planner conditions, if any.
+    pub fn from_name(name: &str) -> Option<ToolClass> {
+        ToolClass::ALL
+            .iter()
+            .copied()
+            .find(|class| class.name() == name)
+    }
+}
+
+fn default_count() -> u32 {
+    1
+}
+
+fn default_chance() -> f32 {
+    1.0
+}
+
+fn default_weight() -> u32 {
+    1
 }
 
 impl ResourceAttribute {
     pub fn is_drop(&self) -> bool {
         match self {
-            ResourceAttribute::Drops(_, _) => true,
+            ResourceAttribute::Drops { .. } => true,
             _ => false,
         }
     }
 }
 
+/// Resource that only ever shows up as an item, e.g. planks made at a
+/// workshop. Turned into a `ResourceType` w/ no attributes when loaded.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ItemType {
+    pub name: String,
+    /// Sprite idx used to render this item.
+    pub sprite: usize,
+    /// How heavy a single one of these is to carry around.
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+    /// Only attributes that make sense for items, e.g. `Tool`.
+    #[serde(default)]
+    pub attributes: Vec<ResourceAttribute>,
+}
+
+impl From<ItemType> for ResourceType {
+    fn from(item: ItemType) -> Self {
+        ResourceType {
+            name: item.name,
+            sprite: item.sprite,
+            weight: item.weight,
+            attributes: item.attributes,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct ResourceType {
     /// Name of this generic resource type
     pub name: String,
     /// Sprite idx used to render this resource.
     pub sprite: usize,
+    /// How heavy a single one of these is to carry around.
+    #[serde(default = "default_weight")]
+    pub weight: u32,
     /// Attributes
     pub attributes: Vec<ResourceAttribute>,
 }
 
+impl ResourceType {
+    /// Tool this turns into when equipped, if it's a tool at all.
+    pub fn tool(&self) -> Option<Tool> {
+        self.attributes
+            .iter()
+            .find_map(|attribute| match attribute {
+                ResourceAttribute::Tool {
+                    class,
+                    chopping,
+                    digging,
+                    durability,
+                } => Some(Tool {
+                    resource: self.name.clone(),
+                    class: *class,
+                    chopping: *chopping,
+                    digging: *digging,
+                    durability: *durability,
+                }),
+                _ => None,
+            })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::{ResourceAttribute, ResourceType};
-    use std::collections::HashMap;
 
     #[test]
     fn test_resource_creation() {
         let tree = ResourceType {
             name: String::from("tree"),
+            sprite: 0,
+            weight: 100,
             attributes: vec![
                 ResourceAttribute::Health(10),
                 // Can have multiple drops
-                ResourceAttribute::Drops(String::from("wood"), 3),
-                ResourceAttribute::Drops(String::from("acorn"), 10),
+                ResourceAttribute::Drops {
+                    resource: String::from("wood"),
+                    min: 2,
+                    max: 3,
+                    chance: 1.0,
+                },
+                ResourceAttribute::Drops {
+                    resource: String::from("acorn"),
+                    min: 1,
+                    max: 10,
+                    chance: 0.1,
+                },
             ],
         };
 
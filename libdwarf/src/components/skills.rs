@@ -0,0 +1,161 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{components::Labor, resources::SimRng};
+
+/// Experience needed to go up a single level.
+const XP_PER_LEVEL: u32 = 100;
+/// Highest level a skill can reach.
+pub const MAX_LEVEL: u32 = 10;
+/// Highest level a new worker can start out with.
+const MAX_STARTING_LEVEL: u32 = 3;
+/// Extra work output per level, e.g. a level 10 woodcutter chops twice as
+/// fast as a beginner.
+const WORK_PER_LEVEL: f32 = 0.1;
+/// Energy saved per level, e.g. a level 10 hauler only uses up half the
+/// energy a beginner would.
+const ENERGY_PER_LEVEL: f32 = 0.05;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+pub enum Skill {
+    Woodcutting,
+    Mining,
+    Hauling,
+    Building,
+    Crafting,
+    Farming,
+    Combat,
+}
+
+impl Skill {
+    pub const ALL: [Skill; 7] = [
+        Skill::Woodcutting,
+        Skill::Mining,
+        Skill::Hauling,
+        Skill::Building,
+        Skill::Crafting,
+        Skill::Farming,
+        Skill::Combat,
+    ];
+
+    /// Kind of work this skill is used for, if any. Anyone can fight.
+    pub fn labor(self) -> Option<Labor> {
+        match self {
+            Skill::Woodcutting | Skill::Mining => Some(Labor::Gathering),
+            Skill::Hauling => Some(Labor::Hauling),
+            Skill::Building => Some(Labor::Building),
+            Skill::Crafting => Some(Labor::Crafting),
+            Skill::Farming => Some(Labor::Farming),
+            Skill::Combat => None,
+        }
+    }
+}
+
+/// How experienced a worker is at each skill. Experience is gained by
+/// finishing actions that use a skill, see `Action::skill`.
+#[derive(Clone, Debug, Default)]
+pub struct Skills {
+    experience: HashMap<Skill, u32>,
+}
+
+impl Skills {
+    /// Skills for a new worker, w/ a random head start in each.
+    pub fn random(rng: &mut SimRng) -> Self {
+        let experience = Skill::ALL
+            .iter()
+            .map(|skill| (*skill, rng.range(0, MAX_STARTING_LEVEL) * XP_PER_LEVEL))
+            .collect();
+
+        Skills { experience }
+    }
+
+    pub fn experience(&self, skill: Skill) -> u32 {
+        self.experience.get(&skill).copied().unwrap_or(0)
+    }
+
+    pub fn level(&self, skill: Skill) -> u32 {
+        (self.experience(skill) / XP_PER_LEVEL).min(MAX_LEVEL)
+    }
+
+    /// Highest level in any of the skills used for <labor>. Work that
+    /// doesn't need a specific labor is level 0 for everyone.
+    pub fn level_for(&self, labor: Option<Labor>) -> u32 {
+        Skill::ALL
+            .iter()
+            .filter(|skill| labor.is_some() && skill.labor() == labor)
+            .map(|skill| self.level(*skill))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Add <xp> to <skill>. Returns the new level if the worker went up a
+    /// level.
+    pub fn gain(&mut self, skill: Skill, xp: u32) -> Option<u32> {
+        let level = self.level(skill);
+        let experience = self.experience.entry(skill).or_insert(0);
+        *experience = experience.saturating_add(xp);
+
+        let new_level = self.level(skill);
+        if new_level > level {
+            Some(new_level)
+        } else {
+            None
+        }
+    }
+
+    /// Scale <amount> of work done using <skill> by how good the worker is
+    /// at it. Always at least 1 so that work gets done eventually.
+    pub fn work(&self, skill: Option<Skill>, amount: u32) -> u32 {
+        let level = skill.map(|skill| self.level(skill)).unwrap_or(0);
+        let scale = 1.0 + level as f32 * WORK_PER_LEVEL;
+        ((amount as f32 * scale).round() as u32).max(1)
+    }
+
+    /// Scale the energy cost of an action using <skill>.
+    pub fn energy_cost(&self, skill: Option<Skill>, cost: f32) -> f32 {
+        let level = skill.map(|skill| self.level(skill)).unwrap_or(0);
+        cost * (1.0 - level as f32 * ENERGY_PER_LEVEL)
+    }
+}
+
+impl fmt::Display for Skills {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let levels: Vec<String> = Skill::ALL
+            .iter()
+            .map(|skill| format!("{:?} {}", skill, self.level(*skill)))
+            .collect();
+        write!(f, "{}", levels.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Skill, Skills, MAX_LEVEL, XP_PER_LEVEL};
+    use crate::{components::Labor, resources::SimRng};
+
+    #[test]
+    fn test_skills() {
+        let mut skills = Skills::default();
+        assert_eq!(skills.level(Skill::Woodcutting), 0);
+        assert_eq!(skills.work(Some(Skill::Woodcutting), 10), 10);
+        assert_eq!(skills.gain(Skill::Woodcutting, XP_PER_LEVEL - 1), None);
+        assert_eq!(skills.gain(Skill::Woodcutting, 1), Some(1));
+        assert_eq!(skills.level_for(Some(Labor::Gathering)), 1);
+        assert_eq!(skills.level_for(Some(Labor::Hauling)), 0);
+
+        // Better workers get more done w/ less energy.
+        skills.gain(Skill::Woodcutting, XP_PER_LEVEL * 100);
+        assert_eq!(skills.level(Skill::Woodcutting), MAX_LEVEL);
+        assert_eq!(skills.work(Some(Skill::Woodcutting), 10), 20);
+        assert_eq!(skills.work(None, 10), 10);
+        assert!(skills.energy_cost(Some(Skill::Woodcutting), 1.0) < 1.0);
+
+        // Same seed, same skills.
+        let a = Skills::random(&mut SimRng::new(7));
+        let b = Skills::random(&mut SimRng::new(7));
+        for skill in Skill::ALL.iter() {
+            assert_eq!(a.experience(*skill), b.experience(*skill));
+        }
+    }
+}
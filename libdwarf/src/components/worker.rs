@@ -1,22 +1,87 @@
-use core::amethyst::ecs::{Component, VecStorage};
-use std::collections::VecDeque;
+use core::amethyst::{
+    ecs::{Component, VecStorage},
+    shrev::EventChannel,
+};
+use serde::Deserialize;
+use std::collections::{HashSet, VecDeque};
 use std::fmt;
 
-use core::{utils::is_near, Point3};
+use core::{log, utils::is_near, Point3};
 use libterrain::Path;
 
 use crate::{
-    components::{EntityInfo, MapObject},
+    components::{
+        Construction, Creature, EntityInfo, Equipment, Health, MapObject, Need, Needs, Skill,
+        Skills,
+    },
+    config::{HealthConfig, NeedsConfig},
+    events::SimEvent,
+    goals::Goal,
+    jobs::JobProgress,
     planner::{Action, Condition, State},
-    resources::{Map, TaskQueue},
+    resources::{Map, Movement, TaskQueue, ITEMS_PER_TILE},
     trigger::TriggerType,
 };
 
+/// Damage dealt to an object each time a beginner works on destroying it.
+const BASE_DAMAGE: u32 = 10;
+/// Odds (0 - 100) of a beginner landing a hit in a fight.
+const BASE_ACCURACY: u32 = 60;
+/// Extra odds of landing a hit per combat level.
+const ACCURACY_PER_LEVEL: u32 = 3;
+/// Experience gained each time an action using a skill is finished.
+const ACTION_XP: u32 = 10;
+
+/// Latest info on whatever the current action is targeting, if anything.
+#[derive(Default)]
+pub struct ActionTarget<'a> {
+    pub object: Option<&'a MapObject>,
+    pub site: Option<&'a Construction>,
+    pub creature: Option<&'a Creature>,
+    /// Where the target is now, for targets that move around.
+    pub position: Option<Point3<u32>>,
+    /// Items on the tile the action is headed to, in case the target has
+    /// been taken by the time the worker gets there.
+    pub items: Vec<(u32, &'a MapObject)>,
+}
+
+/// Kinds of work a worker can be allowed to do.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+pub enum Labor {
+    /// Harvesting resources, e.g. chopping trees.
+    Gathering,
+    /// Picking up & moving items around.
+    Hauling,
+    /// Working on construction sites.
+    Building,
+    /// Making things at workshops.
+    Crafting,
+    /// Tilling, planting & harvesting farm plots.
+    Farming,
+    /// Tracking down & killing wildlife.
+    Hunting,
+}
+
+impl Labor {
+    pub const ALL: [Labor; 6] = [
+        Labor::Gathering,
+        Labor::Hauling,
+        Labor::Building,
+        Labor::Crafting,
+        Labor::Farming,
+        Labor::Hunting,
+    ];
+}
+
 #[derive(Clone)]
 pub struct WorkerAction {
     pub action: Action,
     pub target: Option<u32>,
+    /// Where the target is. Kept up to date for targets that move around,
+    /// e.g. an animal being hunted, see `ActionTarget::position`.
     pub target_pos: Point3<u32>,
+    /// Amount of work put into this action so far.
+    pub progress: u32,
 }
 
 impl fmt::Debug for WorkerAction {
@@ -32,6 +97,26 @@ pub struct Worker {
     /// Energy a worker has. Each action depletes energy. One it reaches, 0
     /// it'll have to wait a couple frames before it can do something else.
     pub energy: f32,
+    /// How hungry, tired, lonely, etc. this worker is.
+    pub needs: Needs,
+    /// Hit points & wounds taken in fights.
+    pub health: Health,
+    /// How good this worker is at each kind of work.
+    pub skills: Skills,
+    /// Tools the worker has equipped.
+    pub equipment: Equipment,
+    /// Passed out from exhaustion. The worker can't do anything until
+    /// they've had some rest.
+    pub collapsed: bool,
+    /// What the worker is currently trying to accomplish.
+    pub goal: Option<Goal>,
+    /// Multi-step job the worker is working through, if any.
+    pub job: Option<JobProgress>,
+    /// Kinds of work this worker will take on.
+    pub labors: HashSet<Labor>,
+    /// Direct order from the player, e.g. move here. Takes priority over
+    /// the worker's own goals.
+    pub order: Option<TriggerType>,
     pub current_action: Option<WorkerAction>,
     // Current path this worker is following.
     pub current_path: Option<Path>,
@@ -43,8 +128,6 @@ pub struct Worker {
     /// The worker needs to MoveTo some location first before they are able
     /// to perform an action.
     pub queue: VecDeque<WorkerAction>,
-    /// Worker's inventory.
-    pub inventory: Vec<u32>,
 }
 
 impl Worker {
@@ -52,31 +135,102 @@ impl Worker {
         Worker {
             id,
             energy: 1.0,
+            needs: Needs::default(),
+            health: Health::new(HealthConfig::default().worker_health),
+            skills: Skills::default(),
+            equipment: Equipment::default(),
+            collapsed: false,
+            goal: None,
+            job: None,
+            labors: Labor::ALL.iter().copied().collect(),
+            order: None,
             current_action: None,
             current_path: None,
             queue: Default::default(),
-            inventory: Default::default(),
         }
     }
 
+    /// Is this worker allowed to do this kind of work? Work that doesn't
+    /// need a specific labor can be done by anyone.
+    pub fn can_do(&self, labor: Option<Labor>) -> bool {
+        match labor {
+            Some(labor) => self.labors.contains(&labor),
+            None => true,
+        }
+    }
+
+    /// Skill used by the action the worker is on or about to start.
+    pub fn action_skill(&self) -> Option<Skill> {
+        self.current_action
+            .as_ref()
+            .or_else(|| self.queue.front())
+            .and_then(|action| action.action.skill)
+    }
+
+    /// Energy it takes this worker to do their next action.
+    pub fn action_cost(&self, base_cost: f32) -> f32 {
+        self.skills.energy_cost(self.action_skill(), base_cost)
+    }
+
+    /// Damage dealt each time this worker hits a creature. Better weapons &
+    /// more experienced fighters hit harder.
+    pub fn attack_damage(&self) -> i32 {
+        let damage = BASE_DAMAGE + self.equipment.weapon_power();
+        self.skills.work(Some(Skill::Combat), damage) as i32
+    }
+
+    /// Odds (0 - 100) of this worker landing a hit.
+    pub fn accuracy(&self) -> u32 {
+        let accuracy = BASE_ACCURACY + self.skills.level(Skill::Combat) * ACCURACY_PER_LEVEL;
+        accuracy.min(100)
+    }
+
     /// Adds workers current state to the planner initial state.
     pub fn add_state(&self, state: &mut State) {
-        state.insert(Condition::Has("axe".to_string()), true);
+        self.equipment.add_state(state);
     }
 
     pub fn do_work(
         &mut self,
         tasks: &mut TaskQueue,
+        events: &mut EventChannel<SimEvent>,
         map: &mut Map,
         entity_info: &mut EntityInfo,
-        target_obj: Option<&MapObject>,
+        target: ActionTarget,
+        needs_config: &NeedsConfig,
     ) {
+        let ActionTarget {
+            object: target_obj,
+            site: target_site,
+            creature: target_creature,
+            position: target_position,
+            items: tile_items,
+        } = target;
+        // Head to wherever the target has moved off to.
+        if let (Some(action), Some(position)) = (self.current_action.as_mut(), target_position) {
+            if action.target_pos != position {
+                action.target_pos = position;
+                self.current_path = None;
+            }
+        }
+
         // An action will be marked as finished once all it's conditions are
         // true.
         let mut finished = true;
+        // Set if the action can't be done anymore.
+        let mut failed = false;
+        // Amount of progress the worker made on a longer task, if any.
+        let mut worked = 0;
         if let Some(action) = &self.current_action {
+            // How much work the worker gets done this tick.
+            let work = self.skills.work(action.action.skill, 1);
+            // Tool this action is done with, if any.
+            let tool = action
+                .action
+                .tool
+                .map(|class| (class, self.equipment.tool(class).cloned()));
             // process action post conditions
-            for (condition, _value) in action.action.post.iter() {
+            for (condition, value) in action.action.post.iter() {
                 match condition {
                     // Attempt to destroy entity
                     Condition::Destroy(_) => {
@@ -87,24 +241,124 @@ impl Worker {
                             let target_obj = target_obj.unwrap();
 
                             if !target_obj.is_destroyed() {
+                                // Better tools make for quicker work.
+                                let damage = match &tool {
+                                    Some((class, Some(tool))) => BASE_DAMAGE + tool.power(*class),
+                                    // Lost our tool along the way.
+                                    Some((_, None)) => {
+                                        failed = true;
+                                        break;
+                                    }
+                                    None => BASE_DAMAGE,
+                                };
+
                                 // Queue damage to this entity
-                                tasks.add_world(TriggerType::DealDamage {
-                                    source: self.id,
-                                    target: *target_id,
-                                    damage: 10,
-                                });
+                                tasks.add_world_from(
+                                    self.id,
+                                    TriggerType::DealDamage {
+                                        source: self.id,
+                                        target: *target_id,
+                                        damage: self.skills.work(action.action.skill, damage)
+                                            as i32,
+                                    },
+                                );
+                                finished = false;
+
+                                if let Some((class, _)) = tool {
+                                    if let Some(broken) = self.equipment.wear(class) {
+                                        events.single_write(SimEvent::ToolBroke {
+                                            worker: self.id,
+                                            tool: broken.resource,
+                                        });
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    // Keep attacking the creature until it's dead.
+                    Condition::Alive(_) => {
+                        let creature = target_creature.filter(|creature| !creature.is_dead());
+                        if let (Some(target_id), Some(_), Some(position)) =
+                            (action.target, creature, target_position)
+                        {
+                            // Got away, chase after it.
+                            if !is_near(&entity_info.pos, &position) {
+                                let stuck = map
+                                    .find_path(&entity_info.pos, &position)
+                                    .map(|mut path| {
+                                        map.step_worker(self.id, &mut entity_info.pos, &mut path)
+                                            == Movement::Stuck
+                                    })
+                                    .unwrap_or(true);
+                                if stuck {
+                                    failed = true;
+                                    break;
+                                }
+
                                 finished = false;
+                                continue;
                             }
+
+                            tasks.add_world_from(
+                                self.id,
+                                TriggerType::Attack {
+                                    source: self.id,
+                                    target: target_id,
+                                    damage: self.attack_damage(),
+                                    accuracy: self.accuracy(),
+                                },
+                            );
+                            finished = false;
                         }
                     }
                     // Pickup item
-                    Condition::Has(_) => {
-                        // Queue picking up this resource
-                        let resource = map.object_map.get(&action.target_pos).unwrap();
-                        tasks.add_world(TriggerType::Take {
-                            target: *resource,
-                            owner: self.id,
-                        });
+                    Condition::Has(resource) if *value => {
+                        // Queue picking up this resource, preferring the one
+                        // we were after if it's still there.
+                        let item = action
+                            .target
+                            .filter(|target| tile_items.iter().any(|(id, _)| id == target))
+                            .or_else(|| {
+                                tile_items
+                                    .iter()
+                                    .rev()
+                                    .find(|(_, object)| object.resource_type.name == *resource)
+                                    .map(|(id, _)| *id)
+                            });
+                        match item {
+                            Some(resource) => tasks.add_world_from(
+                                self.id,
+                                TriggerType::Take {
+                                    target: resource,
+                                    owner: self.id,
+                                },
+                            ),
+                            // Nothing to pick up, someone might've beat us to it.
+                            None => failed = true,
+                        }
+                    }
+                    // Items are given up by some other condition, e.g. storing them.
+                    Condition::Has(_) => {}
+                    // Drop what we're carrying onto a stockpile tile.
+                    Condition::InStockpile(resource) => {
+                        let stored = map
+                            .stack_at(&action.target_pos)
+                            .map_or(0, |stack| stack.items.len());
+                        if stored >= ITEMS_PER_TILE {
+                            // Someone else filled up this spot first.
+                            failed = true;
+                            break;
+                        }
+
+                        tasks.add_world_from(
+                            self.id,
+                            TriggerType::Drop {
+                                owner: self.id,
+                                resource: resource.clone(),
+                                count: (ITEMS_PER_TILE - stored) as u32,
+                                position: action.target_pos,
+                            },
+                        );
                     }
                     // Path closer to this entity
                     Condition::Near(_) => {
@@ -113,23 +367,183 @@ impl Worker {
                             && !is_near(&entity_info.pos, &action.target_pos)
                         {
                             // If not, path from it's current position to the entity.
-                            self.current_path =
-                                Some(map.find_path(&entity_info.pos, &action.target_pos));
+                            match map.find_path(&entity_info.pos, &action.target_pos) {
+                                Some(path) => self.current_path = Some(path),
+                                None => {
+                                    events.single_write(SimEvent::PathFailed {
+                                        worker: self.id,
+                                        from: entity_info.pos,
+                                        to: action.target_pos,
+                                    });
+                                    failed = true;
+                                    break;
+                                }
+                            }
+                        }
+
+                        // Move worker to next location in path, unless we're
+                        // already there.
+                        if is_near(&entity_info.pos, &action.target_pos) {
+                            continue;
                         }
 
-                        // Move worker to next location in path!
                         if let Some(path) = self.current_path.as_mut() {
-                            if let Some(new_pt) = path.pop() {
-                                if is_near(&entity_info.pos, &action.target_pos) {
-                                    // Finished!
-                                    finished = finished && true;
-                                } else {
-                                    // Move laong path.
-                                    let current_pos = entity_info.pos.clone();
-                                    entity_info.pos = new_pt;
-                                    map.move_worker(self.id, current_pos, new_pt);
-                                    finished = false;
+                            if path.is_empty() {
+                                continue;
+                            }
+
+                            let from = entity_info.pos;
+                            match map.step_worker(self.id, &mut entity_info.pos, path) {
+                                Movement::Stuck => {
+                                    events.single_write(SimEvent::PathFailed {
+                                        worker: self.id,
+                                        from,
+                                        to: action.target_pos,
+                                    });
+                                    failed = true;
+                                    break;
                                 }
+                                Movement::Moved | Movement::Waiting => finished = false,
+                            }
+                        }
+                    }
+                    // Hand over materials to a construction site.
+                    Condition::Delivered(resource) => match action.target {
+                        Some(site) if target_site.is_some() => tasks.add_world_from(
+                            self.id,
+                            TriggerType::Deliver {
+                                owner: self.id,
+                                site,
+                                resource: resource.clone(),
+                            },
+                        ),
+                        // Site is gone, e.g. someone else finished it.
+                        _ => failed = true,
+                    },
+                    // Work on a construction site until it's done. Assume it's
+                    // done once it's gone.
+                    Condition::Built(_) => {
+                        if let (Some(site), Some(construction)) = (action.target, target_site) {
+                            if !construction.has_materials() {
+                                failed = true;
+                                break;
+                            }
+
+                            tasks.add_world_from(
+                                self.id,
+                                TriggerType::Build {
+                                    worker: self.id,
+                                    site,
+                                    work,
+                                },
+                            );
+                            finished = false;
+                        }
+                    }
+                    // Work at a workshop until the recipe is done.
+                    Condition::Crafted(_) => {
+                        if let (
+                            Some(Goal::Craft {
+                                recipe,
+                                work: work_needed,
+                                ..
+                            }),
+                            Some(workshop),
+                        ) = (&self.goal, action.target)
+                        {
+                            // Someone tore down the workshop.
+                            if target_obj.is_none() {
+                                failed = true;
+                                break;
+                            }
+
+                            if action.progress + work < *work_needed {
+                                worked = work;
+                                finished = false;
+                            } else {
+                                tasks.add_world_from(
+                                    self.id,
+                                    TriggerType::Craft {
+                                        worker: self.id,
+                                        workshop,
+                                        recipe: recipe.clone(),
+                                    },
+                                );
+                            }
+                        }
+                    }
+                    // Tend to a farm tile.
+                    Condition::Tilled(_) => tasks.add_world_from(
+                        self.id,
+                        TriggerType::Till {
+                            worker: self.id,
+                            position: action.target_pos,
+                        },
+                    ),
+                    Condition::Planted(_) => match &self.goal {
+                        Some(Goal::Farm { crop, .. }) => tasks.add_world_from(
+                            self.id,
+                            TriggerType::Plant {
+                                worker: self.id,
+                                position: action.target_pos,
+                                crop: crop.clone(),
+                            },
+                        ),
+                        _ => failed = true,
+                    },
+                    Condition::Harvested(_) => match action.target {
+                        Some(crop) if target_obj.is_some() => tasks.add_world_from(
+                            self.id,
+                            TriggerType::Harvest {
+                                worker: self.id,
+                                crop,
+                            },
+                        ),
+                        // Someone else got to it first.
+                        _ => failed = true,
+                    },
+                    // Pick up a tool & put it to use.
+                    Condition::Equipped(_) => match action.target {
+                        Some(target) if target_obj.is_some() => tasks.add_world_from(
+                            self.id,
+                            TriggerType::Equip {
+                                worker: self.id,
+                                target,
+                            },
+                        ),
+                        // Someone else got to it first.
+                        _ => failed = true,
+                    },
+                    // Work on satisfying a need, e.g. sleeping.
+                    Condition::Satisfied(name) => {
+                        if let Some(need) = Need::from_name(name) {
+                            // Sleeping on the floor isn't as restful as a bed.
+                            let amount = match need {
+                                Need::Rest if target_obj.is_none() => needs_config.rough_sleep,
+                                _ => 1.0,
+                            };
+                            self.needs.restore(need, amount, needs_config);
+
+                            // Use up anything required to satisfy this need,
+                            // e.g. eating food.
+                            let mut consumed = false;
+                            for (pre, _) in action.action.pre.iter() {
+                                if let Condition::Has(resource) = pre {
+                                    tasks.add_world_from(
+                                        self.id,
+                                        TriggerType::Consume {
+                                            owner: self.id,
+                                            resource: resource.clone(),
+                                        },
+                                    );
+                                    consumed = true;
+                                }
+                            }
+
+                            // Keep at it until the need is satisfied, unless
+                            // something was used up to satisfy it.
+                            if !consumed && !self.needs.is_satisfied(need) {
+                                finished = false;
                             }
                         }
                     }
@@ -138,6 +552,32 @@ impl Worker {
             }
         }
 
+        if worked > 0 {
+            if let Some(action) = self.current_action.as_mut() {
+                action.progress += worked;
+            }
+        }
+
+        if failed {
+            log::info!("Worker {} unable to continue plan", self.id);
+            self.abandon_plan();
+        } else if finished {
+            // Practice makes perfect.
+            let skill = self
+                .current_action
+                .as_ref()
+                .and_then(|action| action.action.skill);
+            if let Some(skill) = skill {
+                if let Some(level) = self.skills.gain(skill, ACTION_XP) {
+                    events.single_write(SimEvent::SkillLevelUp {
+                        worker: self.id,
+                        skill,
+                        level,
+                    });
+                }
+            }
+        }
+
         // Clear action if finished
         if finished {
             self.current_action = None;
@@ -145,7 +585,181 @@ impl Worker {
         }
     }
 
+    /// Drop whatever the worker was doing & follow <order> instead.
+    pub fn give_order(&mut self, order: TriggerType) {
+        self.abandon_plan();
+        self.order = Some(order);
+    }
+
+    /// Take one step along the path the player ordered us to follow. The
+    /// order is dropped once we get there or if there's no way through.
+    pub fn follow_order(
+        &mut self,
+        events: &mut EventChannel<SimEvent>,
+        map: &mut Map,
+        entity_info: &mut EntityInfo,
+    ) {
+        let mut path = match self.order.take() {
+            Some(TriggerType::Move { path, .. }) => path,
+            _ => return,
+        };
+
+        let (next, destination) = match (path.last(), path.first()) {
+            (Some(next), Some(destination)) => (*next, *destination),
+            _ => return,
+        };
+
+        // Someone is standing where we want to end up, so this is as close
+        // as we'll get.
+        if next == destination && map.is_blocked(&next) {
+            return;
+        }
+
+        let from = entity_info.pos;
+        if map.step_worker(self.id, &mut entity_info.pos, &mut path) == Movement::Stuck {
+            events.single_write(SimEvent::PathFailed {
+                worker: self.id,
+                from,
+                to: destination,
+            });
+            return;
+        }
+
+        if !path.is_empty() {
+            self.order = Some(TriggerType::Move {
+                worker: self.id,
+                path,
+            });
+        }
+    }
+
+    /// Called when a world update this worker queued up couldn't be done,
+    /// e.g. someone else picked up the item first.
+    pub fn trigger_failed(&mut self, trigger: &TriggerType) {
+        if matches!(
+            trigger,
+            TriggerType::Take { .. }
+                | TriggerType::Deliver { .. }
+                | TriggerType::Build { .. }
+                | TriggerType::Craft { .. }
+                | TriggerType::Till { .. }
+                | TriggerType::Plant { .. }
+                | TriggerType::Harvest { .. }
+                | TriggerType::Equip { .. }
+        ) {
+            log::info!("Worker {} unable to {:?}, replanning", self.id, trigger);
+            self.abandon_plan();
+        }
+    }
+
+    /// Drop the current plan so the worker comes up with a new one. If they
+    /// were working on a job, the current step will be retried.
+    pub fn abandon_plan(&mut self) {
+        self.goal = None;
+        self.current_action = None;
+        self.current_path = None;
+        self.queue.clear();
+    }
+
     pub fn to_string(&self) -> String {
         format!("({})", self.energy)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{ActionTarget, Worker, WorkerAction};
+    use crate::{
+        components::{EntityInfo, MapObject},
+        config::{NeedsConfig, ResourceConfig},
+        planner::{Action, Condition, State},
+        resources::{Map, TaskQueue},
+        trigger::TriggerType,
+        world::WorldSim,
+    };
+    use core::{
+        amethyst::{
+            ecs::{World, WorldExt},
+            shrev::EventChannel,
+        },
+        Point3,
+    };
+    use libterrain::TerrainChunk;
+
+    #[test]
+    fn test_pick_up_replacement() {
+        let mut world = World::new();
+        WorldSim::builder()
+            .with_data_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/../resources"))
+            .build(&mut world, &TerrainChunk::new(4, 4), 4, 4)
+            .unwrap();
+        let resources = world.read_resource::<ResourceConfig>().clone();
+        let wood = MapObject::new(resources.get("wood").unwrap());
+        let wheat = MapObject::new(resources.get("wheat").unwrap());
+
+        let mut post = State::new();
+        post.insert(Condition::Has("wood".to_string()), true);
+        let pick_up = WorkerAction {
+            action: Action {
+                name: "pick_up_wood".to_string(),
+                cost: 1,
+                pre: State::new(),
+                post,
+                skill: None,
+                tool: None,
+            },
+            // Someone else already took the wood the worker was after.
+            target: Some(10),
+            target_pos: Point3::new(0, 0, 0),
+            progress: 0,
+        };
+
+        let mut map = world.write_resource::<Map>();
+        let mut events = EventChannel::new();
+        let mut entity_info = EntityInfo {
+            pos: Point3::new(0, 0, 0),
+            z_offset: 1.0,
+        };
+
+        // Only wheat left, not what the worker needs.
+        let mut tasks = TaskQueue::default();
+        let mut worker = Worker::new(1);
+        worker.current_action = Some(pick_up.clone());
+        worker.do_work(
+            &mut tasks,
+            &mut events,
+            &mut map,
+            &mut entity_info,
+            ActionTarget {
+                items: vec![(11, &wheat)],
+                ..ActionTarget::default()
+            },
+            &NeedsConfig::default(),
+        );
+        assert!(tasks.world.is_empty());
+        assert!(worker.current_action.is_none());
+
+        // Any other wood on the tile will do.
+        let mut tasks = TaskQueue::default();
+        let mut worker = Worker::new(1);
+        worker.current_action = Some(pick_up);
+        worker.do_work(
+            &mut tasks,
+            &mut events,
+            &mut map,
+            &mut entity_info,
+            ActionTarget {
+                items: vec![(12, &wood), (11, &wheat)],
+                ..ActionTarget::default()
+            },
+            &NeedsConfig::default(),
+        );
+        assert_eq!(
+            tasks.world.pop_front().map(|record| record.trigger),
+            Some(TriggerType::Take {
+                target: 12,
+                owner: 1,
+            })
+        );
+    }
+}
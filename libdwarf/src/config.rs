@@ -1,26 +1,82 @@
 use ron::de::from_reader;
-use serde::Deserialize;
-use std::{collections::HashMap, fs::File};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{
+    collections::HashMap,
+    fs::File,
+    path::{Path, PathBuf},
+};
 
-use crate::components::ResourceType;
-use core::log;
+use crate::{
+    components::{ItemType, Need, ResourceType},
+    error::Error,
+    resources::{Climate, Season, WeatherKind},
+};
+
+/// Read & deserialize a RON file.
+pub fn read_ron<T: DeserializeOwned>(input_path: &Path) -> Result<T, Error> {
+    let f = File::open(input_path).map_err(|source| Error::Io {
+        path: input_path.to_path_buf(),
+        source,
+    })?;
+
+    from_reader(f).map_err(|source| Error::Parse {
+        path: input_path.to_path_buf(),
+        source,
+    })
+}
+
+/// Locations of the data files the simulation was loaded from. Used to
+/// reload them while the sim is running. Configs that were passed in directly
+/// rather than loaded from disk have no path and are never reloaded.
+#[derive(Clone, Debug, Default)]
+pub struct ConfigPaths {
+    pub actions: Option<PathBuf>,
+    pub buildings: Option<PathBuf>,
+    pub creatures: Option<PathBuf>,
+    pub crops: Option<PathBuf>,
+    pub jobs: Option<PathBuf>,
+    pub recipes: Option<PathBuf>,
+    pub resources: Option<PathBuf>,
+    pub sim_config: Option<PathBuf>,
+}
+
+/// Format of the resources data file.
+#[derive(Deserialize)]
+#[serde(rename = "ResourceConfig")]
+struct ResourceList {
+    map: HashMap<String, ResourceType>,
+    #[serde(default)]
+    items: HashMap<String, ItemType>,
+}
 
 #[derive(Clone, Debug, Deserialize)]
+#[serde(from = "ResourceList")]
 pub struct ResourceConfig {
+    /// Every resource type, including the item-only ones.
     pub map: HashMap<String, ResourceType>,
 }
 
+impl From<ResourceList> for ResourceConfig {
+    fn from(list: ResourceList) -> Self {
+        let mut map = list.map;
+        for (id, item) in list.items {
+            map.entry(id).or_insert_with(|| item.into());
+        }
+
+        ResourceConfig { map }
+    }
+}
+
 impl ResourceConfig {
-    pub fn load(input_path: &str) -> Self {
-        let f = File::open(input_path).expect("Failed opening file");
-        let config: ResourceConfig = match from_reader(f) {
-            Ok(x) => x,
-            Err(e) => {
-                log::error!("Failed to load ResourceConfig: {}", e);
-                std::process::exit(1);
-            }
-        };
-        config
+    pub fn load<P: AsRef<Path>>(input_path: P) -> Result<Self, Error> {
+        read_ron(input_path.as_ref())
+    }
+
+    /// Look up a resource type by name.
+    pub fn get(&self, name: &str) -> Result<&ResourceType, Error> {
+        self.map
+            .get(name)
+            .ok_or_else(|| Error::UnknownResource(name.to_string()))
     }
 }
 
@@ -29,18 +85,226 @@ pub struct WorldConfig {
     /// How fast workers regain energy
     pub action_cost: f32,
     pub worker_stamina: f32,
+    /// How quickly needs decay & what happens when they're left unmet.
+    #[serde(default)]
+    pub needs: NeedsConfig,
+    /// How much of each resource the colony should keep around. Idle workers
+    /// will go gather more when the colony is running low.
+    #[serde(default)]
+    pub stock_targets: HashMap<String, u32>,
+    /// Max weight a worker can carry around.
+    #[serde(default = "default_carry_weight")]
+    pub carry_weight: u32,
+    /// Tools each new worker shows up w/ already equipped, e.g. "axe".
+    #[serde(default)]
+    pub starting_tools: Vec<String>,
+    /// How long days & seasons last.
+    #[serde(default)]
+    pub calendar: CalendarConfig,
+    /// What the weather's like & how it affects the colony.
+    #[serde(default)]
+    pub weather: WeatherConfig,
+    /// How much punishment workers can take & how quickly they recover.
+    #[serde(default)]
+    pub health: HealthConfig,
+}
+
+fn default_carry_weight() -> u32 {
+    10
+}
+
+/// Length of the in-game day & year, see `Calendar`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct CalendarConfig {
+    /// Seconds of sim time a full day & night takes.
+    pub day_length: f32,
+    pub days_per_season: u32,
+}
+
+impl Default for CalendarConfig {
+    fn default() -> Self {
+        CalendarConfig {
+            day_length: 600.0,
+            days_per_season: 10,
+        }
+    }
+}
+
+/// How often the weather changes & what it does to the map & workers, see
+/// `Weather`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct WeatherConfig {
+    /// Shortest & longest a spell of weather lasts, in seconds.
+    pub min_duration: f32,
+    pub max_duration: f32,
+    /// Odds of each kind of weather in a climate & season. Anything that's
+    /// missing is always clear.
+    pub chances: HashMap<Climate, HashMap<Season, Vec<(WeatherKind, u32)>>>,
+    /// Snow cover piled up per second while it's snowing.
+    pub snow_fall: f32,
+    /// Snow cover melted per second the rest of the time.
+    pub snow_melt: f32,
+    /// Number of puddles workers can drink from that form when it rains.
+    pub puddles: u32,
+    /// Rest lost per second by workers caught outside in the cold.
+    pub cold_drain: f32,
+    /// Thirst lost per second by workers caught outside in a heat wave.
+    pub heat_drain: f32,
+}
+
+impl Default for WeatherConfig {
+    fn default() -> Self {
+        WeatherConfig {
+            min_duration: 60.0,
+            max_duration: 300.0,
+            chances: HashMap::new(),
+            snow_fall: 0.01,
+            snow_melt: 0.005,
+            puddles: 8,
+            cold_drain: 0.005,
+            heat_drain: 0.005,
+        }
+    }
+}
+
+/// Hit points & how wounds bleed & heal, see `Health`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct HealthConfig {
+    /// Hit points each worker starts out w/.
+    pub worker_health: f32,
+    /// Hit points lost per second for each point of damage taken, until
+    /// the wound stops bleeding.
+    pub bleed_rate: f32,
+    /// How quickly bleeding slows down on its own, per second.
+    pub clot_rate: f32,
+    /// Hit points regained per second once nothing's bleeding.
+    pub heal_rate: f32,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        HealthConfig {
+            worker_health: 100.0,
+            bleed_rate: 0.02,
+            clot_rate: 0.1,
+            heal_rate: 0.2,
+        }
+    }
+}
+
+/// How quickly a single need decays & recovers.
+#[derive(Clone, Debug, Deserialize)]
+pub struct NeedRates {
+    /// How much the need decays per second.
+    pub decay: f32,
+    /// How much a single action restores, e.g. eating a meal.
+    pub recovery: f32,
+}
+
+/// Thresholds are compared against how satisfied a need is, from 0.0
+/// (desperate) to 1.0 (fully satisfied).
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct NeedsConfig {
+    pub hunger: NeedRates,
+    pub thirst: NeedRates,
+    pub rest: NeedRates,
+    pub social: NeedRates,
+    /// Below this, workers drop low priority work to take care of the need.
+    pub seek_threshold: f32,
+    /// Below this, workers regain energy slower.
+    pub slow_threshold: f32,
+    /// How fast slowed down workers regain energy, e.g. 0.5 for half speed.
+    pub slow_factor: f32,
+    /// Below this, workers stop working & only look after themselves.
+    pub stop_threshold: f32,
+    /// How much of the usual rest sleeping anywhere but a bed gives.
+    pub rough_sleep: f32,
+    /// Rest a worker needs before getting back up after collapsing.
+    pub wake_threshold: f32,
+}
+
+impl NeedsConfig {
+    pub fn rates(&self, need: Need) -> &NeedRates {
+        match need {
+            Need::Hunger => &self.hunger,
+            Need::Thirst => &self.thirst,
+            Need::Rest => &self.rest,
+            Need::Social => &self.social,
+        }
+    }
+}
+
+impl Default for NeedsConfig {
+    fn default() -> Self {
+        NeedsConfig {
+            hunger: NeedRates {
+                decay: 1.0 / 300.0,
+                recovery: 0.5,
+            },
+            thirst: NeedRates {
+                decay: 1.0 / 240.0,
+                recovery: 0.5,
+            },
+            rest: NeedRates {
+                decay: 1.0 / 600.0,
+                recovery: 0.05,
+            },
+            social: NeedRates {
+                decay: 1.0 / 400.0,
+                recovery: 0.1,
+            },
+            seek_threshold: 0.3,
+            slow_threshold: 0.15,
+            slow_factor: 0.5,
+            stop_threshold: 0.05,
+            rough_sleep: 0.5,
+            wake_threshold: 0.5,
+        }
+    }
 }
 
 impl WorldConfig {
-    pub fn load(input_path: &str) -> Self {
-        let f = File::open(input_path).expect("Failed opening file");
-        let config: WorldConfig = match from_reader(f) {
-            Ok(x) => x,
-            Err(e) => {
-                log::error!("Failed to load WorldConfig: {}", e);
-                std::process::exit(1);
-            }
-        };
-        config
+    pub fn load<P: AsRef<Path>>(input_path: P) -> Result<Self, Error> {
+        read_ron(input_path.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ResourceConfig, WorldConfig};
+    use crate::error::Error;
+    use std::path::PathBuf;
+
+    fn data_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../resources")
+    }
+
+    #[test]
+    fn test_load_resource_config() {
+        let config = ResourceConfig::load(data_dir().join("data/resources.ron")).unwrap();
+        assert!(config.get("tree").is_ok());
+        assert!(config.get("wood").is_ok());
+        // Item-only resources are loaded as well.
+        assert!(config.get("plank").is_ok());
+        assert!(config.get("unobtainium").is_err());
+    }
+
+    #[test]
+    fn test_load_world_config() {
+        let config = WorldConfig::load(data_dir().join("sim_config.ron")).unwrap();
+        assert!(config.action_cost > 0.0);
+        assert!(config.worker_stamina > 0.0);
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        match WorldConfig::load(data_dir().join("does_not_exist.ron")) {
+            Err(Error::Io { .. }) => {}
+            other => panic!("Expected an Io error, got {:?}", other),
+        }
     }
 }
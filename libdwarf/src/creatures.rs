@@ -0,0 +1,140 @@
+///
+/// Creatures that roam the map on their own, e.g. goblins or deer.
+///
+/// Each species spawns on the biomes it prefers, at the time of day it's
+/// active. Hostile creatures go after any worker that comes within range,
+/// workers either fight back or run, see `Goal::combat`. Wildlife keeps its
+/// distance from workers & can be hunted down for its carcass.
+///
+use libterrain::Biome;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::{config::read_ron, error::Error, resources::Calendar};
+
+/// Time of day a species is out & about.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+pub enum ActiveTime {
+    Day,
+    Night,
+    Always,
+}
+
+/// When & how often a species shows up on the map.
+#[derive(Clone, Debug, Deserialize)]
+pub struct SpawnRule {
+    pub active: ActiveTime,
+    /// Odds of one showing up each second while active.
+    pub chance: f32,
+    /// Most that can be on the map at once.
+    pub max: u32,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct CreatureType {
+    /// Display name, e.g. "goblin".
+    pub name: String,
+    /// Sprite idx in the npc sprite sheet.
+    pub sprite: usize,
+    pub health: i32,
+    /// Tiles moved per second.
+    pub speed: f32,
+    /// Damage dealt each time it attacks.
+    pub damage: i32,
+    /// Odds (0 - 100) of an attack landing.
+    pub accuracy: u32,
+    /// How close a worker has to get before it goes after them. Creatures
+    /// w/ no aggression never attack.
+    pub aggression: u32,
+    /// How close a worker can get before it runs off. Only passive creatures
+    /// flee.
+    #[serde(default)]
+    pub flee_range: u32,
+    /// Object left behind when it dies, defaults to a plain corpse.
+    #[serde(default)]
+    pub corpse: Option<String>,
+    /// Biomes it spawns on.
+    pub biomes: Vec<Biome>,
+    pub spawn: SpawnRule,
+}
+
+impl CreatureType {
+    pub fn is_hostile(&self) -> bool {
+        self.aggression > 0
+    }
+
+    /// Does it run off when workers get close?
+    pub fn is_skittish(&self) -> bool {
+        !self.is_hostile() && self.flee_range > 0
+    }
+
+    pub fn spawns_on(&self, biome: &Biome) -> bool {
+        self.biomes.contains(biome)
+    }
+
+    /// Is it the right time of day for this species to show up?
+    pub fn is_active(&self, calendar: &Calendar) -> bool {
+        match self.spawn.active {
+            ActiveTime::Day => !calendar.is_night(),
+            ActiveTime::Night => calendar.is_night(),
+            ActiveTime::Always => true,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CreatureLibrary {
+    creatures: HashMap<String, CreatureType>,
+}
+
+impl CreatureLibrary {
+    pub fn load<P: AsRef<Path>>(input_path: P) -> Result<Self, Error> {
+        read_ron(input_path.as_ref())
+    }
+
+    pub fn get(&self, name: &str) -> Result<&CreatureType, Error> {
+        self.creatures
+            .get(name)
+            .ok_or_else(|| Error::UnknownCreature(name.to_string()))
+    }
+
+    /// Ids of every species, sorted by name.
+    pub fn creature_ids(&self) -> Vec<&String> {
+        let mut ids: Vec<&String> = self.creatures.keys().collect();
+        ids.sort();
+        ids
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CreatureLibrary;
+    use crate::{config::CalendarConfig, resources::Calendar};
+    use libterrain::Biome;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_load_creatures() {
+        let data_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../resources/data");
+        let library = CreatureLibrary::load(data_dir.join("creatures.ron")).unwrap();
+        for id in library.creature_ids() {
+            let creature = library.get(id).unwrap();
+            assert!(creature.health > 0, "{}", id);
+            assert!(!creature.biomes.is_empty(), "{}", id);
+        }
+
+        // Goblins only come out at night.
+        let goblin = library.get("goblin").unwrap();
+        assert!(goblin.is_hostile());
+        assert!(goblin.spawns_on(&Biome::GRASSLAND));
+        assert!(!goblin.is_active(&Calendar::new(&CalendarConfig::default())));
+        assert!(library.get("dragon").is_err());
+
+        // Deer keep their distance & can be butchered.
+        let deer = library.get("deer").unwrap();
+        assert!(!deer.is_hostile());
+        assert!(deer.is_skittish());
+        assert_eq!(deer.corpse.as_deref(), Some("carcass"));
+    }
+}
@@ -0,0 +1,133 @@
+///
+/// Crops that can be grown on farm plots, e.g. wheat.
+///
+/// Workers till the tiles in a plot, plant the crop the plot was designated
+/// for & come back to harvest it once it's fully grown. Crops only grow in
+/// some seasons & can only be planted on some biomes.
+///
+use libterrain::Biome;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::{
+    components::{ResourceAttribute, ResourceType},
+    config::read_ron,
+    error::Error,
+    resources::Season,
+};
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct CropType {
+    /// Display name, e.g. "wheat".
+    pub name: String,
+    /// Sprite idx for each growth stage, from just planted to ready to
+    /// harvest.
+    pub stages: Vec<usize>,
+    /// Number of sim ticks it takes to be ready to harvest. Time spent out
+    /// of season doesn't count.
+    pub growth: u32,
+    /// Biomes this can be planted on.
+    pub biomes: Vec<Biome>,
+    /// Seasons this can be planted & grows in.
+    pub seasons: Vec<Season>,
+    /// Resources harvested from a fully grown crop.
+    pub yields: HashMap<String, u32>,
+}
+
+impl CropType {
+    /// Growth stage after <growth> ticks. The last stage is only reached
+    /// once the crop is ready to harvest.
+    pub fn stage(&self, growth: u32) -> usize {
+        let last = self.stages.len().saturating_sub(1);
+        if growth >= self.growth {
+            return last;
+        }
+
+        growth as usize * last / self.growth as usize
+    }
+
+    /// Sprite idx to render after <growth> ticks.
+    pub fn sprite(&self, growth: u32) -> usize {
+        self.stages
+            .get(self.stage(growth))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn grows_in(&self, season: Season) -> bool {
+        self.seasons.contains(&season)
+    }
+
+    pub fn grows_on(&self, biome: &Biome) -> bool {
+        self.biomes.contains(biome)
+    }
+
+    /// Resource type of the planted crop. Crops have health so they're
+    /// never mistaken for items to be hauled off, & a name of their own so
+    /// they don't count towards the stock of what they yield.
+    pub fn resource_type(&self) -> ResourceType {
+        ResourceType {
+            name: format!("{} plant", self.name),
+            sprite: self.sprite(0),
+            weight: 1,
+            attributes: vec![ResourceAttribute::Health(1)],
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CropLibrary {
+    crops: HashMap<String, CropType>,
+}
+
+impl CropLibrary {
+    pub fn load<P: AsRef<Path>>(input_path: P) -> Result<Self, Error> {
+        read_ron(input_path.as_ref())
+    }
+
+    pub fn get(&self, name: &str) -> Result<&CropType, Error> {
+        self.crops
+            .get(name)
+            .ok_or_else(|| Error::UnknownCrop(name.to_string()))
+    }
+
+    /// Ids of every crop, sorted by name.
+    pub fn crop_ids(&self) -> Vec<&String> {
+        let mut ids: Vec<&String> = self.crops.keys().collect();
+        ids.sort();
+        ids
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CropLibrary;
+    use crate::{config::ResourceConfig, resources::Season};
+    use libterrain::Biome;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_load_crops() {
+        let data_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../resources/data");
+        let library = CropLibrary::load(data_dir.join("crops.ron")).unwrap();
+        let resources = ResourceConfig::load(data_dir.join("resources.ron")).unwrap();
+
+        // Everything harvested should be a known resource.
+        for id in library.crop_ids() {
+            let crop = library.get(id).unwrap();
+            assert!(!crop.stages.is_empty());
+            for resource in crop.yields.keys() {
+                assert!(resources.get(resource).is_ok(), "{} in {}", resource, id);
+            }
+        }
+
+        let wheat = library.get("wheat").unwrap();
+        assert!(wheat.grows_on(&Biome::GRASSLAND));
+        assert!(!wheat.grows_in(Season::Winter));
+        assert_eq!(wheat.stage(0), 0);
+        assert_eq!(wheat.stage(wheat.growth - 1), wheat.stages.len() - 2);
+        assert_eq!(wheat.stage(wheat.growth), wheat.stages.len() - 1);
+        assert!(library.get("mandrake").is_err());
+    }
+}
@@ -0,0 +1,59 @@
+use std::{error, fmt, io, path::PathBuf};
+
+/// Errors that can occur while setting up or running the simulation.
+#[derive(Debug)]
+pub enum Error {
+    /// Unable to open or read a data file.
+    Io { path: PathBuf, source: io::Error },
+    /// A data file is not valid RON or doesn't match the expected format.
+    Parse {
+        path: PathBuf,
+        source: ron::de::Error,
+    },
+    /// Something referenced a resource type that isn't in the `ResourceConfig`.
+    UnknownResource(String),
+    /// Actions use more unique conditions than the planner can keep track of.
+    TooManyConditions(usize),
+    /// A job or method references a compound task that doesn't exist.
+    UnknownTask(String),
+    /// Something referenced a blueprint that isn't in the `BlueprintLibrary`.
+    UnknownBlueprint(String),
+    /// Something referenced a recipe that isn't in the `RecipeBook`.
+    UnknownRecipe(String),
+    /// Something referenced a crop that isn't in the `CropLibrary`.
+    UnknownCrop(String),
+    /// Something referenced a creature that isn't in the `CreatureLibrary`.
+    UnknownCreature(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => {
+                write!(f, "Failed opening {}: {}", path.display(), source)
+            }
+            Error::Parse { path, source } => {
+                write!(f, "Failed parsing {}: {}", path.display(), source)
+            }
+            Error::UnknownResource(name) => write!(f, "Unknown resource type '{}'", name),
+            Error::TooManyConditions(max) => {
+                write!(f, "Actions use more than {} unique conditions", max)
+            }
+            Error::UnknownTask(name) => write!(f, "Unknown task '{}'", name),
+            Error::UnknownBlueprint(name) => write!(f, "Unknown blueprint '{}'", name),
+            Error::UnknownRecipe(name) => write!(f, "Unknown recipe '{}'", name),
+            Error::UnknownCrop(name) => write!(f, "Unknown crop '{}'", name),
+            Error::UnknownCreature(name) => write!(f, "Unknown creature '{}'", name),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Parse { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,110 @@
+///
+/// Outcomes of the simulation, published on the `EventChannel<SimEvent>`
+/// resource so the game & other tools can react to what's going on without
+/// having to poll the world.
+///
+use core::Point3;
+
+use crate::{
+    components::Skill,
+    resources::{JobId, WeatherKind},
+    trigger::{EntityId, TriggerType},
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SimEvent {
+    /// An object was removed from the map, e.g. a tree being chopped down.
+    ObjectDestroyed {
+        id: EntityId,
+        resource: String,
+        position: Point3<u32>,
+    },
+    ItemPickedUp {
+        item: EntityId,
+        owner: EntityId,
+        resource: String,
+    },
+    WorkerSpawned {
+        id: EntityId,
+        position: Point3<u32>,
+    },
+    /// A worker finished a job from the job board.
+    JobCompleted {
+        job: JobId,
+        worker: EntityId,
+        task: TriggerType,
+    },
+    /// A construction site was finished & replaced w/ what it was building.
+    ConstructionFinished {
+        site: EntityId,
+        blueprint: String,
+        position: Point3<u32>,
+    },
+    /// A worker made something at a workshop.
+    ItemsCrafted {
+        worker: EntityId,
+        recipe: String,
+    },
+    /// A worker picked a fully grown crop.
+    CropHarvested {
+        worker: EntityId,
+        crop: String,
+        position: Point3<u32>,
+    },
+    /// A worker passed out from exhaustion.
+    WorkerCollapsed {
+        worker: EntityId,
+    },
+    /// A worker got back up after collapsing.
+    WorkerRecovered {
+        worker: EntityId,
+    },
+    /// A tool a worker had equipped wore out.
+    ToolBroke {
+        worker: EntityId,
+        tool: String,
+    },
+    /// A worker got better at <skill>.
+    SkillLevelUp {
+        worker: EntityId,
+        skill: Skill,
+        level: u32,
+    },
+    /// A new spell of weather rolled in.
+    WeatherChanged {
+        weather: WeatherKind,
+    },
+    /// A creature showed up on the map.
+    CreatureSpawned {
+        id: EntityId,
+        creature: String,
+        position: Point3<u32>,
+    },
+    /// A creature died, either in a fight or bleeding out afterwards.
+    CreatureKilled {
+        id: EntityId,
+        creature: String,
+        position: Point3<u32>,
+        /// Whoever landed the last hit, if anyone.
+        killer: Option<EntityId>,
+    },
+    /// A worker died, leaving behind a corpse & everything they carried.
+    WorkerDied {
+        worker: EntityId,
+        position: Point3<u32>,
+        killer: Option<EntityId>,
+    },
+    /// A worker or creature took a swing at another.
+    Attacked {
+        attacker: EntityId,
+        target: EntityId,
+        /// Damage dealt, `None` if it missed.
+        damage: Option<i32>,
+    },
+    /// A worker couldn't find a way to get somewhere.
+    PathFailed {
+        worker: EntityId,
+        from: Point3<u32>,
+        to: Point3<u32>,
+    },
+}
@@ -0,0 +1,872 @@
+///
+/// Goal selection for idle workers.
+///
+/// Each time a worker runs out of things to do, every potential goal is given
+/// a utility score based on the worker's needs and the state of the colony.
+/// The highest scoring goal that the planner can find a plan for wins.
+///
+use std::collections::{HashMap, HashSet};
+
+use core::Point3;
+use libterrain::TerrainChunk;
+
+use crate::{
+    components::{Construction, Health, Inventory, Labor, MapObject, Need, Needs, ToolClass},
+    config::NeedsConfig,
+    jobs::Step,
+    planner::{Condition, State},
+    recipes::Recipe,
+};
+
+/// Score given to tasks assigned by the player.
+const PLAYER_TASK_SCORE: f32 = 0.5;
+/// Score given to picking up loose items lying around the map.
+const HAUL_SCORE: f32 = 0.3;
+/// Score given to putting away items the worker is carrying.
+const STORE_SCORE: f32 = 0.35;
+/// Score given to bringing materials to & working on construction sites.
+const CONSTRUCTION_SCORE: f32 = 0.45;
+/// Score given to working on production orders.
+const CRAFT_SCORE: f32 = 0.4;
+/// Score given to tending to farm plots.
+const FARM_SCORE: f32 = 0.4;
+/// Max score given to gathering a resource, used when there is none left.
+const GATHER_SCORE: f32 = 0.4;
+/// Score given to getting some rest at night.
+const NIGHT_REST_SCORE: f32 = 0.6;
+/// Score given to needs that have been left unmet for too long.
+const URGENT_NEED_SCORE: f32 = 0.9;
+/// Score given to fighting or running from a hostile creature, before
+/// anything else.
+pub const COMBAT_SCORE: f32 = 1.0;
+/// Most hits a worker is willing to trade to take a creature down. Anything
+/// tougher than that & they'll run for it.
+const MAX_FIGHT_HITS: i32 = 3;
+/// Workers w/ less than this much of their health left run from any fight.
+const FLEE_HEALTH: f32 = 0.3;
+/// Goals scoring below this aren't worth doing.
+const MIN_SCORE: f32 = 0.05;
+
+/// Work that needs doing on a farm tile.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FarmWork {
+    Till,
+    Plant,
+    /// Pick the fully grown crop entity.
+    Harvest(u32),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Goal {
+    /// Satisfy one of the worker's needs, e.g. sleeping or eating.
+    Satisfy {
+        need: Need,
+        /// Entity that helps satisfy this need, e.g. another worker to chat with.
+        target: Option<u32>,
+        /// Where to go to satisfy this need, e.g. a bed or some water.
+        position: Option<Point3<u32>>,
+    },
+    /// Harvest <source> to gather more <resource>, e.g. chop a tree for wood.
+    Gather {
+        target: u32,
+        position: Point3<u32>,
+        source: String,
+        resource: String,
+    },
+    /// Pick up a loose item lying around the map.
+    Haul {
+        target: u32,
+        position: Point3<u32>,
+        resource: String,
+    },
+    /// Pick up <resource> needed for work the worker is going to do next,
+    /// e.g. materials for a construction site.
+    Fetch {
+        target: u32,
+        position: Point3<u32>,
+        resource: String,
+        labor: Labor,
+    },
+    /// Put away <resource> the worker is carrying in a stockpile.
+    Store {
+        resource: String,
+        position: Point3<u32>,
+    },
+    /// Bring <resource> the worker is carrying to a construction site.
+    Supply {
+        site: u32,
+        position: Point3<u32>,
+        resource: String,
+    },
+    /// Put in the work to finish a construction site.
+    Build { site: u32, position: Point3<u32> },
+    /// Make <recipe> at a workshop w/ the inputs the worker is carrying.
+    Craft {
+        workshop: u32,
+        position: Point3<u32>,
+        recipe: String,
+        /// Amount of work it takes to make the recipe.
+        work: u32,
+    },
+    /// Tend to a tile in a farm plot growing <crop>.
+    Farm {
+        position: Point3<u32>,
+        crop: String,
+        work: FarmWork,
+    },
+    /// Take down the hostile creature <target> that's come after the worker.
+    Fight { target: u32, position: Point3<u32> },
+    /// Run from a hostile creature that's too tough to take on, to
+    /// somewhere safer, e.g. the stockpile or another worker.
+    Flee { position: Point3<u32> },
+    /// Track down & kill the animal <target> for its carcass.
+    Hunt { target: u32, position: Point3<u32> },
+    /// Work on a step of the worker's current job.
+    Step {
+        name: String,
+        /// Object this step is focused on & its type, if any.
+        target: Option<(u32, Point3<u32>, String)>,
+        desired: State,
+    },
+}
+
+impl Goal {
+    /// Goal for satisfying <need>, going after the closest unclaimed thing
+    /// that helps, e.g. food or a bed. Returns `None` if there's no way to
+    /// satisfy it right now.
+    pub fn satisfy(
+        need: Need,
+        worker_id: u32,
+        inventory: Option<&Inventory>,
+        current_pos: &Point3<u32>,
+        ctx: &GoalContext,
+    ) -> Option<Goal> {
+        let nearest = |name: &str| {
+            ctx.nearest_object(current_pos, |object| object.resource_type.name == name)
+                .map(|(id, pos, _)| (Some(*id), Some(*pos)))
+        };
+
+        let (target, position) = match need {
+            // Eat what we're carrying.
+            Need::Hunger if inventory.map(|inv| inv.has(need.target())).unwrap_or(false) => {
+                (None, None)
+            }
+            // Nothing to eat or drink, no point in trying.
+            Need::Hunger => nearest(need.target())?,
+            Need::Thirst => (None, Some(ctx.nearest_water(current_pos)?)),
+            // Sleep on the floor if there's no bed.
+            Need::Rest => nearest(need.target()).unwrap_or((None, None)),
+            Need::Social => match ctx.nearest_worker(worker_id, current_pos) {
+                Some((id, pos)) => (Some(id), Some(pos)),
+                None => (None, None),
+            },
+        };
+
+        Some(Goal::Satisfy {
+            need,
+            target,
+            position,
+        })
+    }
+
+    /// Goal for dealing w/ the closest hostile creature within range of
+    /// the worker, if any. Workers fight anything they can take down in a
+    /// few hits of <damage> & run from anything else, or when they're badly
+    /// hurt, unless there's nowhere to run to.
+    pub fn combat(
+        current_pos: &Point3<u32>,
+        damage: i32,
+        health: &Health,
+        ctx: &GoalContext,
+    ) -> Option<Goal> {
+        let threat = ctx
+            .threats
+            .iter()
+            .filter(|threat| threat.in_range(current_pos))
+            .min_by_key(|threat| TerrainChunk::heuristic(current_pos, &threat.position))?;
+
+        let fight = Goal::Fight {
+            target: threat.id,
+            position: threat.position,
+        };
+        let hits = (threat.health + damage - 1) / damage.max(1);
+        if hits <= MAX_FIGHT_HITS && health.fraction() >= FLEE_HEALTH {
+            return Some(fight);
+        }
+
+        // Head for whichever refuge is out of the creature's reach & the
+        // farthest away from it.
+        let refuge = ctx
+            .stockpiled
+            .iter()
+            .chain(ctx.workers.iter().map(|(_, pos)| pos))
+            .filter(|pt| !threat.in_range(pt))
+            .max_by_key(|pt| TerrainChunk::heuristic(&threat.position, pt));
+
+        match refuge {
+            Some(position) => Some(Goal::Flee {
+                position: *position,
+            }),
+            // Cornered.
+            None => Some(fight),
+        }
+    }
+
+    /// Goal for working on <step>, going after the closest unclaimed object
+    /// of the type the step needs. Returns `None` if there isn't one.
+    pub fn from_step(step: &Step, current_pos: &Point3<u32>, ctx: &GoalContext) -> Option<Goal> {
+        let target = match step.target.as_deref() {
+            // Construction sites aren't objects, go for the closest one that
+            // still needs what the step delivers or is ready to be built.
+            Some("site") => {
+                let (id, pos, _) = ctx
+                    .sites
+                    .iter()
+                    .filter(|(_, _, site)| {
+                        step.goal.keys().all(|condition| match condition {
+                            Condition::Delivered(resource) => site.needs(resource) > 0,
+                            Condition::Built(_) => site.has_materials(),
+                            _ => true,
+                        })
+                    })
+                    .min_by_key(|(_, pos, _)| TerrainChunk::heuristic(current_pos, pos))?;
+                Some((*id, *pos, "site".to_string()))
+            }
+            Some(name) => {
+                let (id, pos, _) =
+                    ctx.nearest_object(current_pos, |object| object.resource_type.name == name)?;
+                Some((*id, *pos, name.to_string()))
+            }
+            None => None,
+        };
+
+        Some(Goal::Step {
+            name: step.name.clone(),
+            target,
+            desired: step.goal.clone(),
+        })
+    }
+
+    /// Entity this goal is focused on, if any.
+    pub fn target(&self) -> Option<u32> {
+        match self {
+            Goal::Satisfy { target, .. } => *target,
+            Goal::Gather { target, .. }
+            | Goal::Haul { target, .. }
+            | Goal::Fetch { target, .. } => Some(*target),
+            Goal::Store { .. } => None,
+            Goal::Supply { site, .. } | Goal::Build { site, .. } => Some(*site),
+            Goal::Craft { workshop, .. } => Some(*workshop),
+            Goal::Fight { target, .. } | Goal::Hunt { target, .. } => Some(*target),
+            Goal::Flee { .. } => None,
+            Goal::Farm {
+                work: FarmWork::Harvest(crop),
+                ..
+            } => Some(*crop),
+            Goal::Farm { .. } => None,
+            Goal::Step { target, .. } => target.as_ref().map(|(id, _, _)| *id),
+        }
+    }
+
+    /// Location this goal is focused on, if any.
+    pub fn target_pos(&self) -> Option<Point3<u32>> {
+        match self {
+            Goal::Satisfy { position, .. } => *position,
+            Goal::Gather { position, .. }
+            | Goal::Haul { position, .. }
+            | Goal::Fetch { position, .. }
+            | Goal::Store { position, .. }
+            | Goal::Supply { position, .. }
+            | Goal::Build { position, .. }
+            | Goal::Craft { position, .. }
+            | Goal::Farm { position, .. }
+            | Goal::Fight { position, .. }
+            | Goal::Hunt { position, .. }
+            | Goal::Flee { position } => Some(*position),
+            Goal::Step { target, .. } => target.as_ref().map(|(_, pos, _)| *pos),
+        }
+    }
+
+    /// Labor a worker needs to have enabled to pick this goal on their own.
+    pub fn labor(&self) -> Option<Labor> {
+        match self {
+            Goal::Gather { .. } => Some(Labor::Gathering),
+            Goal::Haul { .. } | Goal::Store { .. } | Goal::Supply { .. } => Some(Labor::Hauling),
+            Goal::Build { .. } => Some(Labor::Building),
+            Goal::Craft { .. } => Some(Labor::Crafting),
+            Goal::Farm { .. } => Some(Labor::Farming),
+            Goal::Hunt { .. } => Some(Labor::Hunting),
+            Goal::Fetch { labor, .. } => Some(*labor),
+            _ => None,
+        }
+    }
+
+    /// Name of the thing this goal is focused on in planner conditions, if
+    /// any, e.g. `Near("tree")`.
+    pub fn subject(&self) -> Option<&str> {
+        let name = match self {
+            Goal::Satisfy { position: None, .. } => return None,
+            Goal::Satisfy { need, .. } => need.target(),
+            Goal::Gather { source, .. } => source,
+            Goal::Haul { resource, .. } | Goal::Fetch { resource, .. } => resource,
+            Goal::Store { .. } => "stockpile",
+            Goal::Supply { .. } | Goal::Build { .. } => "site",
+            Goal::Craft { .. } => "workshop",
+            Goal::Farm { .. } => "field",
+            Goal::Fight { .. } => "creature",
+            Goal::Flee { .. } => "safety",
+            Goal::Hunt { .. } => "animal",
+            Goal::Step {
+                target: Some((_, _, name)),
+                ..
+            } => name,
+            Goal::Step { .. } => return None,
+        };
+
+        Some(name)
+    }
+
+    /// Add goal specific conditions to the worker's current state.
+    pub fn add_state(&self, state: &mut State, current_pos: &Point3<u32>) {
+        let name = match self.subject() {
+            Some(name) => name,
+            None => return,
+        };
+
+        let is_near = self
+            .target_pos()
+            .map(|pos| core::utils::is_near(&pos, current_pos))
+            .unwrap_or(false);
+        state.insert(Condition::Near(name.to_string()), is_near);
+    }
+
+    /// The state the worker wants to reach to complete this goal.
+    pub fn desired_state(&self) -> State {
+        let mut state = State::new();
+        match self {
+            Goal::Satisfy { need, .. } => {
+                state.insert(Condition::Satisfied(need.name().to_string()), true);
+            }
+            Goal::Gather {
+                source, resource, ..
+            } => {
+                state.insert(Condition::Destroy(source.clone()), true);
+                state.insert(Condition::Has(resource.clone()), true);
+            }
+            Goal::Haul { resource, .. } | Goal::Fetch { resource, .. } => {
+                state.insert(Condition::Has(resource.clone()), true);
+            }
+            Goal::Store { resource, .. } => {
+                state.insert(Condition::InStockpile(resource.clone()), true);
+            }
+            Goal::Supply { resource, .. } => {
+                state.insert(Condition::Delivered(resource.clone()), true);
+            }
+            Goal::Build { .. } => {
+                state.insert(Condition::Built("site".to_string()), true);
+            }
+            Goal::Craft { .. } => {
+                state.insert(Condition::Crafted("workshop".to_string()), true);
+            }
+            Goal::Farm { work, .. } => {
+                let field = "field".to_string();
+                let condition = match work {
+                    FarmWork::Till => Condition::Tilled(field),
+                    FarmWork::Plant => Condition::Planted(field),
+                    FarmWork::Harvest(_) => Condition::Harvested(field),
+                };
+                state.insert(condition, true);
+            }
+            Goal::Fight { .. } => {
+                state.insert(Condition::Alive("creature".to_string()), false);
+            }
+            Goal::Flee { .. } => {
+                state.insert(Condition::Near("safety".to_string()), true);
+            }
+            Goal::Hunt { .. } => {
+                state.insert(Condition::Alive("animal".to_string()), false);
+            }
+            Goal::Step { desired, .. } => return desired.clone(),
+        }
+
+        state
+    }
+}
+
+/// Stockpile tile w/ room for more items.
+#[derive(Clone, Debug)]
+pub struct StorageSpot {
+    pub position: Point3<u32>,
+    /// Resource types the stockpile takes. Anything goes if empty.
+    pub accepts: HashSet<String>,
+    /// Resource already piled up here, if any. Only the same kind of item
+    /// can be added to the pile.
+    pub resource: Option<String>,
+}
+
+/// Hostile creature workers need to watch out for.
+#[derive(Clone, Debug)]
+pub struct Threat {
+    pub id: u32,
+    pub position: Point3<u32>,
+    pub health: i32,
+    /// How close a worker can get before it comes after them.
+    pub range: u32,
+}
+
+impl Threat {
+    pub fn in_range(&self, pt: &Point3<u32>) -> bool {
+        TerrainChunk::heuristic(&self.position, pt) <= self.range as usize
+    }
+}
+
+/// Snapshot of the world used to score goals.
+#[derive(Default)]
+pub struct GoalContext<'a> {
+    /// Objects currently on the map, i.e. not in someone's inventory.
+    pub objects: Vec<(u32, Point3<u32>, &'a MapObject)>,
+    /// Every worker on the map.
+    pub workers: Vec<(u32, Point3<u32>)>,
+    /// Colony-wide count of each resource, both on the map and carried.
+    pub stock: HashMap<String, u32>,
+    /// How much of each resource we'd like to keep around.
+    pub stock_targets: HashMap<String, u32>,
+    /// Entities that some worker is already going after.
+    pub claimed: HashSet<u32>,
+    /// Stockpile tiles that have room for more items.
+    pub storage: Vec<StorageSpot>,
+    /// Every tile that's part of a stockpile.
+    pub stockpiled: HashSet<Point3<u32>>,
+    /// Construction sites that haven't been finished yet.
+    pub sites: Vec<(u32, Point3<u32>, &'a Construction)>,
+    /// Materials construction sites still need, minus what's already on the
+    /// way there.
+    pub demand: HashMap<String, u32>,
+    /// Recipes w/ production orders that still need work, in the order
+    /// they were given.
+    pub recipes: Vec<(String, &'a Recipe)>,
+    /// Farm tiles that need tending & the crop their plot grows.
+    pub farm_work: Vec<(Point3<u32>, String, FarmWork)>,
+    /// Farm tiles that some worker is already tending to.
+    pub tended: HashSet<Point3<u32>>,
+    /// Tiles where workers can get a drink.
+    pub water: Vec<Point3<u32>>,
+    /// Hostile creatures roaming the map.
+    pub threats: Vec<Threat>,
+    /// Wildlife roaming the map that can be hunted.
+    pub animals: Vec<(u32, Point3<u32>)>,
+    /// Workers head to bed once it gets dark.
+    pub is_night: bool,
+    /// Too rough out to work outside, e.g. during a storm.
+    pub is_storm: bool,
+}
+
+impl<'a> GoalContext<'a> {
+    pub fn object(&self, id: u32) -> Option<&(u32, Point3<u32>, &'a MapObject)> {
+        self.objects.iter().find(|(oid, _, _)| *oid == id)
+    }
+
+    /// Find the closest unclaimed object matching <predicate>.
+    fn nearest_object<P>(
+        &self,
+        from: &Point3<u32>,
+        predicate: P,
+    ) -> Option<&(u32, Point3<u32>, &'a MapObject)>
+    where
+        P: Fn(&MapObject) -> bool,
+    {
+        self.objects
+            .iter()
+            .filter(|(id, _, object)| !self.claimed.contains(id) && predicate(object))
+            .min_by_key(|(_, pos, _)| TerrainChunk::heuristic(from, pos))
+    }
+
+    /// Find the closest unclaimed tool of <class> lying around.
+    pub fn nearest_tool(
+        &self,
+        from: &Point3<u32>,
+        class: ToolClass,
+    ) -> Option<&(u32, Point3<u32>, &'a MapObject)> {
+        self.nearest_object(from, |object| {
+            object.is_item()
+                && object
+                    .resource_type
+                    .tool()
+                    .map(|tool| tool.class == class)
+                    .unwrap_or(false)
+        })
+    }
+
+    /// Find the closest stockpile tile that has room for <resource>.
+    fn nearest_storage(&self, from: &Point3<u32>, resource: &str) -> Option<&StorageSpot> {
+        self.storage
+            .iter()
+            .filter(|spot| spot.accepts.is_empty() || spot.accepts.contains(resource))
+            .filter(|spot| match &spot.resource {
+                Some(stored) => stored == resource,
+                None => true,
+            })
+            .min_by_key(|spot| TerrainChunk::heuristic(from, &spot.position))
+    }
+
+    /// Take whatever <goal> is taking care of out of the context, so we
+    /// don't send everyone off to fetch the same materials or tend to the
+    /// same farm tile.
+    pub fn on_the_way(&mut self, goal: &Goal) {
+        match goal {
+            Goal::Fetch {
+                resource,
+                labor: Labor::Hauling,
+                ..
+            } => {
+                if let Some(demand) = self.demand.get_mut(resource) {
+                    *demand = demand.saturating_sub(1);
+                }
+            }
+            Goal::Farm { position, .. } => {
+                self.tended.insert(*position);
+            }
+            _ => {}
+        }
+    }
+
+    fn nearest_water(&self, from: &Point3<u32>) -> Option<Point3<u32>> {
+        self.water
+            .iter()
+            .min_by_key(|pt| TerrainChunk::heuristic(from, pt))
+            .copied()
+    }
+
+    fn nearest_worker(&self, worker_id: u32, from: &Point3<u32>) -> Option<(u32, Point3<u32>)> {
+        self.workers
+            .iter()
+            .filter(|(id, _)| *id != worker_id)
+            .min_by_key(|(_, pos)| TerrainChunk::heuristic(from, pos))
+            .copied()
+    }
+}
+
+/// Score each potential goal for a worker, highest scoring goal first.
+/// - `player_goal` is the next task assigned by the player, if any.
+pub fn score_goals(
+    worker_id: u32,
+    needs: &Needs,
+    inventory: Option<&Inventory>,
+    current_pos: &Point3<u32>,
+    ctx: &GoalContext,
+    config: &NeedsConfig,
+    player_goal: Option<Goal>,
+) -> Vec<(f32, Goal)> {
+    let mut scored = Vec::new();
+
+    // Needs become more urgent the closer they get to 0. Needs left unmet
+    // for too long come before anything else.
+    for need in Need::ALL.iter() {
+        let goal = match Goal::satisfy(*need, worker_id, inventory, current_pos, ctx) {
+            Some(goal) => goal,
+            None => continue,
+        };
+
+        let urgency = 1.0 - needs.get(*need);
+        let score = if needs.is_urgent(*need, config) {
+            URGENT_NEED_SCORE
+        } else if *need == Need::Rest && ctx.is_night {
+            NIGHT_REST_SCORE.max(urgency * urgency)
+        } else {
+            urgency * urgency
+        };
+        scored.push((score, goal));
+    }
+
+    // Too hungry, thirsty or tired to get any work done, or it's too rough
+    // out to be working outside.
+    if needs.is_desperate(config) || ctx.is_storm {
+        scored.retain(|(score, _)| *score >= MIN_SCORE);
+        scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        return scored;
+    }
+
+    if let Some(goal) = player_goal {
+        scored.push((PLAYER_TASK_SCORE, goal));
+    }
+
+    // Put away anything we're carrying.
+    if let Some(inventory) = inventory {
+        for stack in inventory.stacks.iter() {
+            if let Some(spot) = ctx.nearest_storage(current_pos, &stack.resource) {
+                scored.push((
+                    STORE_SCORE,
+                    Goal::Store {
+                        resource: stack.resource.clone(),
+                        position: spot.position,
+                    },
+                ));
+                break;
+            }
+        }
+    }
+
+    let room = inventory
+        .map(|inventory| inventory.max_weight.saturating_sub(inventory.weight()))
+        .unwrap_or(0);
+
+    // Bring over any materials we're carrying that a construction site needs.
+    if let Some(inventory) = inventory {
+        let site = ctx
+            .sites
+            .iter()
+            .flat_map(|(id, pos, site)| {
+                site.missing()
+                    .into_iter()
+                    .filter(|(resource, _)| inventory.has(resource))
+                    .map(move |(resource, _)| (*id, *pos, resource))
+            })
+            .min_by_key(|(_, pos, _)| TerrainChunk::heuristic(current_pos, pos));
+
+        if let Some((site, position, resource)) = site {
+            scored.push((
+                CONSTRUCTION_SCORE,
+                Goal::Supply {
+                    site,
+                    position,
+                    resource: resource.clone(),
+                },
+            ));
+        }
+    }
+
+    // Fetch materials for construction sites, even out of a stockpile.
+    let material = ctx.nearest_object(current_pos, |object| {
+        object.is_item()
+            && object.resource_type.weight <= room
+            && ctx
+                .demand
+                .get(&object.resource_type.name)
+                .copied()
+                .unwrap_or(0)
+                > 0
+    });
+    if let Some((target, position, object)) = material {
+        scored.push((
+            CONSTRUCTION_SCORE,
+            Goal::Fetch {
+                target: *target,
+                position: *position,
+                resource: object.resource_type.name.clone(),
+                labor: Labor::Hauling,
+            },
+        ));
+    }
+
+    // Work on the closest site that has everything it needs.
+    let site = ctx
+        .sites
+        .iter()
+        .filter(|(id, _, site)| !ctx.claimed.contains(id) && site.has_materials())
+        .min_by_key(|(_, pos, _)| TerrainChunk::heuristic(current_pos, pos));
+    if let Some((site, position, _)) = site {
+        scored.push((
+            CONSTRUCTION_SCORE,
+            Goal::Build {
+                site: *site,
+                position: *position,
+            },
+        ));
+    }
+
+    // Work on the first production order we can, fetching the inputs first.
+    for (id, recipe) in ctx.recipes.iter() {
+        let workshop = ctx.nearest_object(current_pos, |object| {
+            object.is_workshop() && object.resource_type.name == recipe.workshop
+        });
+        let (workshop, position, _) = match workshop {
+            Some(workshop) => workshop,
+            None => continue,
+        };
+
+        let carried = |resource: &str| inventory.map_or(0, |inventory| inventory.count(resource));
+        let missing = recipe
+            .inputs
+            .iter()
+            .find(|(resource, count)| carried(resource) < **count);
+
+        let goal = match missing {
+            None => Some(Goal::Craft {
+                workshop: *workshop,
+                position: *position,
+                recipe: id.clone(),
+                work: recipe.work,
+            }),
+            Some((resource, _)) => ctx
+                .nearest_object(current_pos, |object| {
+                    object.is_item()
+                        && object.resource_type.name == *resource
+                        && object.resource_type.weight <= room
+                })
+                .map(|(target, position, _)| Goal::Fetch {
+                    target: *target,
+                    position: *position,
+                    resource: resource.clone(),
+                    labor: Labor::Crafting,
+                }),
+        };
+
+        if let Some(goal) = goal {
+            scored.push((CRAFT_SCORE, goal));
+            break;
+        }
+    }
+
+    // Tend to the closest farm tile that needs it.
+    let farm_work = ctx
+        .farm_work
+        .iter()
+        .filter(|(position, _, _)| !ctx.tended.contains(position))
+        .min_by_key(|(position, _, _)| TerrainChunk::heuristic(current_pos, position));
+    if let Some((position, crop, work)) = farm_work {
+        scored.push((
+            FARM_SCORE,
+            Goal::Farm {
+                position: *position,
+                crop: crop.clone(),
+                work: *work,
+            },
+        ));
+    }
+
+    // Clean up loose items that have somewhere to go & that we can carry.
+    let loose_item = ctx
+        .objects
+        .iter()
+        .filter(|(id, pos, object)| {
+            object.is_item()
+                && object.resource_type.weight <= room
+                && !ctx.claimed.contains(id)
+                && !ctx.stockpiled.contains(pos)
+                && ctx
+                    .nearest_storage(pos, &object.resource_type.name)
+                    .is_some()
+        })
+        .min_by_key(|(_, pos, _)| TerrainChunk::heuristic(current_pos, pos));
+
+    if let Some((target, position, object)) = loose_item {
+        scored.push((
+            HAUL_SCORE,
+            Goal::Haul {
+                target: *target,
+                position: *position,
+                resource: object.resource_type.name.clone(),
+            },
+        ));
+    }
+
+    // Gather resources we're running low on.
+    for (resource, target_amount) in ctx.stock_targets.iter() {
+        let amount = ctx.stock.get(resource).copied().unwrap_or(0);
+        if amount >= *target_amount {
+            continue;
+        }
+
+        let shortage = 1.0 - amount as f32 / *target_amount as f32;
+        if let Some((target, position, object)) =
+            ctx.nearest_object(current_pos, |object| object.drops(resource))
+        {
+            scored.push((
+                GATHER_SCORE * shortage,
+                Goal::Gather {
+                    target: *target,
+                    position: *position,
+                    source: object.resource_type.name.clone(),
+                    resource: resource.clone(),
+                },
+            ));
+        }
+    }
+
+    scored.retain(|(score, _)| *score >= MIN_SCORE);
+    scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+    scored
+}
+
+#[cfg(test)]
+mod test {
+    use super::{score_goals, Goal, GoalContext};
+    use crate::{
+        components::{MapObject, Need, Needs},
+        config::{NeedsConfig, ResourceConfig},
+    };
+    use core::Point3;
+    use std::path::PathBuf;
+
+    /// Goals an unencumbered worker at the origin would pick, best first.
+    fn ranked(needs: &Needs, ctx: &GoalContext) -> Vec<Goal> {
+        let config = NeedsConfig::default();
+        score_goals(1, needs, None, &Point3::new(0, 0, 0), ctx, &config, None)
+            .into_iter()
+            .map(|(_, goal)| goal)
+            .collect()
+    }
+
+    #[test]
+    fn test_score_goals() {
+        let path =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../resources/data/resources.ron");
+        let resources = ResourceConfig::load(path).unwrap();
+        let food = MapObject::new(resources.get("food").unwrap());
+        let bed = MapObject::new(resources.get("bed").unwrap());
+        let tree = MapObject::new(resources.get("tree").unwrap());
+
+        let mut ctx = GoalContext {
+            objects: vec![
+                (1, Point3::new(2, 0, 0), &food),
+                (2, Point3::new(0, 2, 0), &bed),
+                (3, Point3::new(0, 1, 0), &tree),
+            ],
+            ..GoalContext::default()
+        };
+        ctx.stock_targets.insert("wood".to_string(), 10);
+        let gather = Goal::Gather {
+            target: 3,
+            position: Point3::new(0, 1, 0),
+            source: "tree".to_string(),
+            resource: "wood".to_string(),
+        };
+        let eat = Goal::Satisfy {
+            need: Need::Hunger,
+            target: Some(1),
+            position: Some(Point3::new(2, 0, 0)),
+        };
+        let sleep = Goal::Satisfy {
+            need: Need::Rest,
+            target: Some(2),
+            position: Some(Point3::new(0, 2, 0)),
+        };
+
+        // Nothing to take care of, get some work done.
+        assert_eq!(ranked(&Needs::default(), &ctx), vec![gather.clone()]);
+
+        // Hungry workers eat before going back to work.
+        let hungry = Needs {
+            hunger: 0.2,
+            ..Needs::default()
+        };
+        assert_eq!(ranked(&hungry, &ctx), vec![eat.clone(), gather.clone()]);
+
+        // A bit tired isn't enough to stop working, unless it's night out.
+        let tired = Needs {
+            rest: 0.6,
+            ..Needs::default()
+        };
+        assert_eq!(ranked(&tired, &ctx), vec![gather.clone(), sleep.clone()]);
+        ctx.is_night = true;
+        assert_eq!(ranked(&tired, &ctx), vec![sleep.clone(), gather]);
+
+        // Too hungry to do anything else.
+        let starving = Needs {
+            hunger: 0.01,
+            rest: 0.6,
+            ..Needs::default()
+        };
+        assert_eq!(ranked(&starving, &ctx), vec![eat, sleep]);
+    }
+}
@@ -0,0 +1,300 @@
+///
+/// Hierarchical task networks (HTN) for multi-step jobs.
+///
+/// A job is a list of tasks. Compound tasks are broken down into subtasks
+/// using the first method whose pre-conditions hold, until we're left with a
+/// list of steps. Each step is a goal state that's handed off to the
+/// `Planner` once the worker gets to it.
+///
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::path::Path;
+
+use crate::{components::Labor, config::read_ron, error::Error, planner::State};
+
+/// Max number of compound tasks we'll expand while decomposing a job. Guards
+/// against tasks that (indirectly) include themselves.
+const MAX_DEPTH: usize = 32;
+
+/// Primitive task, solved by the GOAP planner.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Step {
+    pub name: String,
+    /// Type of object the worker should go after, e.g. "tree".
+    pub target: Option<String>,
+    /// State the worker should be in once the step is done.
+    pub goal: State,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub enum Task {
+    Step {
+        name: String,
+        #[serde(default)]
+        target: Option<String>,
+        goal: State,
+    },
+    /// Compound task, referenced by name.
+    Task(String),
+}
+
+/// One way of accomplishing a compound task.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Method {
+    pub name: String,
+    /// Conditions that must hold for this method to be used.
+    #[serde(default)]
+    pub pre: State,
+    pub subtasks: Vec<Task>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Job {
+    /// Display name, e.g. "build wall".
+    pub name: String,
+    /// Labor a worker needs to have enabled to take this job, if any.
+    #[serde(default)]
+    pub labor: Option<Labor>,
+    pub tasks: Vec<Task>,
+}
+
+/// Format of the jobs data file.
+#[derive(Deserialize)]
+struct JobList {
+    /// Compound tasks & the methods to accomplish each of them, in order of
+    /// preference.
+    tasks: HashMap<String, Vec<Method>>,
+    jobs: HashMap<String, Job>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(try_from = "JobList")]
+pub struct JobLibrary {
+    tasks: HashMap<String, Vec<Method>>,
+    jobs: HashMap<String, Job>,
+}
+
+impl TryFrom<JobList> for JobLibrary {
+    type Error = Error;
+
+    fn try_from(list: JobList) -> Result<Self, Self::Error> {
+        let library = JobLibrary {
+            tasks: list.tasks,
+            jobs: list.jobs,
+        };
+
+        // Make sure every compound task we reference exists.
+        let methods = library.tasks.values().flatten();
+        let subtasks = methods.flat_map(|method| method.subtasks.iter());
+        let job_tasks = library.jobs.values().flat_map(|job| job.tasks.iter());
+        for task in subtasks.chain(job_tasks) {
+            if let Task::Task(name) = task {
+                if !library.tasks.contains_key(name) {
+                    return Err(Error::UnknownTask(name.clone()));
+                }
+            }
+        }
+
+        Ok(library)
+    }
+}
+
+impl JobLibrary {
+    pub fn load<P: AsRef<Path>>(input_path: P) -> Result<Self, Error> {
+        read_ron(input_path.as_ref())
+    }
+
+    pub fn get(&self, job: &str) -> Option<&Job> {
+        self.jobs.get(job)
+    }
+
+    /// Ids of every job, sorted by name.
+    pub fn job_ids(&self) -> Vec<&String> {
+        let mut ids: Vec<&String> = self.jobs.keys().collect();
+        ids.sort();
+        ids
+    }
+
+    /// Break down <job> into a list of steps, starting from <state>. Returns
+    /// `None` if the job doesn't exist or can't be done from this state.
+    pub fn decompose(&self, job: &str, state: &State) -> Option<JobProgress> {
+        let job = self.get(job)?;
+        let steps = self.decompose_tasks(&job.tasks, state.clone(), 0)?;
+        let mut progress = JobProgress {
+            name: job.name.clone(),
+            steps,
+            step: 0,
+        };
+        progress.skip_done(state);
+        Some(progress)
+    }
+
+    fn decompose_tasks(&self, tasks: &[Task], mut state: State, depth: usize) -> Option<Vec<Step>> {
+        let (task, rest) = match tasks.split_first() {
+            Some(split) => split,
+            None => return Some(Vec::new()),
+        };
+
+        match task {
+            Task::Step { name, target, goal } => {
+                // Assume the step works out & continue from there.
+                for (condition, value) in goal.iter() {
+                    state.insert(condition.clone(), *value);
+                }
+
+                let mut steps = vec![Step {
+                    name: name.clone(),
+                    target: target.clone(),
+                    goal: goal.clone(),
+                }];
+                steps.extend(self.decompose_tasks(rest, state, depth)?);
+                Some(steps)
+            }
+            Task::Task(name) => {
+                if depth >= MAX_DEPTH {
+                    return None;
+                }
+
+                // Try each method in order, backtracking if the rest of the
+                // job can't be done with it.
+                for method in self.tasks.get(name)?.iter() {
+                    let applies = method
+                        .pre
+                        .iter()
+                        .all(|(condition, value)| state.get(condition) == Some(value));
+                    if !applies {
+                        continue;
+                    }
+
+                    let mut expanded = method.subtasks.clone();
+                    expanded.extend(rest.iter().cloned());
+                    if let Some(steps) = self.decompose_tasks(&expanded, state.clone(), depth + 1) {
+                        return Some(steps);
+                    }
+                }
+
+                None
+            }
+        }
+    }
+}
+
+/// A worker's progress through a job.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JobProgress {
+    pub name: String,
+    pub steps: Vec<Step>,
+    /// Number of steps that have been finished.
+    pub step: usize,
+}
+
+impl JobProgress {
+    /// Step the worker is on, if there's any left.
+    pub fn next_step(&self) -> Option<&Step> {
+        self.steps.get(self.step)
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.step >= self.steps.len()
+    }
+
+    /// Skip over steps whose goal already holds in <state>, e.g. the worker
+    /// picked up the wood a step was going to fetch along the way. There'd be
+    /// nothing for the planner to do for them.
+    pub fn skip_done(&mut self, state: &State) {
+        while let Some(step) = self.next_step() {
+            let is_done = step
+                .goal
+                .iter()
+                .all(|(condition, value)| state.get(condition) == Some(value));
+            if !is_done {
+                break;
+            }
+
+            self.step += 1;
+        }
+    }
+}
+
+impl fmt::Display for JobProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Job: {} (step {}/{})",
+            self.name,
+            (self.step + 1).min(self.steps.len()),
+            self.steps.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::JobLibrary;
+    use crate::planner::{Condition, State};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_load_and_decompose() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../resources/data/jobs.ron");
+        let library = JobLibrary::load(path).unwrap();
+
+        let mut progress = library.decompose("clear_trees", &State::new()).unwrap();
+        assert_eq!(progress.steps.len(), 2);
+        assert_eq!(progress.to_string(), "Job: clear trees (step 1/2)");
+        progress.step = 1;
+        assert_eq!(progress.next_step(), progress.steps.get(1));
+        assert_eq!(progress.to_string(), "Job: clear trees (step 2/2)");
+        progress.step = 2;
+        assert!(progress.is_done());
+
+        // Already carrying wood, so there's nothing to do.
+        let mut state = State::new();
+        state.insert(Condition::Has("wood".to_string()), true);
+        let progress = library.decompose("gather_wood", &state).unwrap();
+        assert!(progress.is_done());
+
+        assert!(library.decompose("does_not_exist", &state).is_none());
+    }
+
+    #[test]
+    fn test_build_job() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../resources/data/jobs.ron");
+        let library = JobLibrary::load(path).unwrap();
+
+        // Chop a tree for wood, haul it to the site & build.
+        let mut progress = library.decompose("build", &State::new()).unwrap();
+        let names: Vec<&str> = progress
+            .steps
+            .iter()
+            .map(|step| step.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["chop tree", "haul to site", "construct"]);
+        assert_eq!(progress.to_string(), "Job: build (step 1/3)");
+
+        // Picked up some wood before getting to the tree.
+        let mut state = State::new();
+        state.insert(Condition::Has("wood".to_string()), true);
+        progress.skip_done(&state);
+        assert_eq!(progress.step, 0);
+
+        // Tree's gone too, nothing left to do for the first step.
+        state.insert(Condition::Destroy("tree".to_string()), true);
+        progress.skip_done(&state);
+        assert_eq!(progress.step, 1);
+        progress.skip_done(&state);
+        assert_eq!(progress.step, 1);
+
+        // Already carrying wood, so go straight to the site.
+        state.remove(&Condition::Destroy("tree".to_string()));
+        let progress = library.decompose("build", &state).unwrap();
+        let names: Vec<&str> = progress
+            .steps
+            .iter()
+            .map(|step| step.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["haul to site", "construct"]);
+    }
+}
@@ -1,11 +1,21 @@
+pub mod buildings;
 pub mod components;
 pub mod config;
+pub mod creatures;
+pub mod crops;
+mod error;
+pub mod events;
+pub mod goals;
+pub mod jobs;
 pub mod planner;
+pub mod recipes;
 pub mod resources;
 pub mod systems;
 pub mod trigger;
 pub mod world;
 
+pub use error::Error;
+
 use core::amethyst::core::{
     ecs::{DispatcherBuilder, World},
     SystemBundle,
@@ -19,12 +29,22 @@ impl<'a, 'b> SystemBundle<'a, 'b> for WorldSimBundle {
         _world: &mut World,
         builder: &mut DispatcherBuilder<'a, 'b>,
     ) -> Result<(), core::amethyst::Error> {
-        builder.add(systems::WorkerSystem, "worker_sim", &[]);
-        builder.add(systems::ObjectSystem, "object_sim", &[]);
+        builder.add(systems::ConfigReloadSystem::default(), "config_reload", &[]);
+        builder.add(systems::WorkerSystem, "worker_sim", &["config_reload"]);
+        builder.add(systems::ObjectSystem, "object_sim", &["config_reload"]);
+        builder.add(systems::CropSystem, "crop_sim", &["config_reload"]);
+        builder.add(systems::WeatherSystem, "weather_sim", &["config_reload"]);
+        builder.add(systems::CreatureSystem, "creature_sim", &["config_reload"]);
         builder.add(
             systems::WorldUpdateSystem::default(),
             "world_updates",
-            &["worker_sim", "object_sim"],
+            &[
+                "worker_sim",
+                "object_sim",
+                "crop_sim",
+                "weather_sim",
+                "creature_sim",
+            ],
         );
 
         builder.add(systems::TimeTickSystem, "game_tick", &["world_updates"]);
@@ -1,11 +1,15 @@
-use ron::de::from_reader;
 use serde::Deserialize;
-use std::fs::File;
+use std::path::Path;
 
-use core::log;
-use libpath::find_path;
+use crate::{
+    components::{Skill, ToolClass},
+    config::read_ron,
+    error::Error,
+};
+use libpath::find_path_with;
 
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::fmt;
 use std::hash::{Hash, Hasher};
 
@@ -25,16 +29,130 @@ pub enum Condition {
     Near(String),
     // Agent can see `x`
     Visible(String),
+    // Agent's need `x` has been satisfied, e.g. they've had enough rest.
+    Satisfied(String),
+    // Agent has put `x` away in a stockpile.
+    InStockpile(String),
+    // Agent has handed over `x` to a construction site.
+    Delivered(String),
+    // Agent has finished building `x`.
+    Built(String),
+    // Agent has finished making something at `x`.
+    Crafted(String),
+    // Agent has tilled the soil at `x`.
+    Tilled(String),
+    // Agent has planted a crop at `x`.
+    Planted(String),
+    // Agent has picked the crop at `x`.
+    Harvested(String),
+    // Agent has a tool of class `x` equipped.
+    Equipped(String),
 }
 
+/// Human-friendly state, used when writing actions & goals.
 pub type State = HashMap<Condition, bool>;
 
+/// Number of u64 words used to store a `BitState`.
+const STATE_WORDS: usize = 4;
+/// Max number of unique conditions a planner can know about.
+pub const MAX_CONDITIONS: usize = STATE_WORDS * 64;
+
+/// Index of an interned `Condition`, see `Planner::condition_id`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ConditionId(usize);
+
+impl ConditionId {
+    fn word(self) -> usize {
+        self.0 / 64
+    }
+
+    fn bit(self) -> u64 {
+        1 << (self.0 % 64)
+    }
+}
+
+/// Compact version of `State` used while planning.
+///
+/// `mask` marks which conditions we know about and `values` holds the value
+/// for each of those conditions. Conditions outside of the mask are always
+/// stored as 0 so that equal states also hash the same.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct BitState {
+    values: [u64; STATE_WORDS],
+    mask: [u64; STATE_WORDS],
+}
+
+impl BitState {
+    pub fn get(&self, id: ConditionId) -> Option<bool> {
+        if self.mask[id.word()] & id.bit() == 0 {
+            None
+        } else {
+            Some(self.values[id.word()] & id.bit() != 0)
+        }
+    }
+
+    pub fn set(&mut self, id: ConditionId, value: bool) {
+        self.mask[id.word()] |= id.bit();
+        if value {
+            self.values[id.word()] |= id.bit();
+        } else {
+            self.values[id.word()] &= !id.bit();
+        }
+    }
+
+    /// Number of conditions in <other> that don't match this state. Conditions
+    /// that are missing from this state are counted as mismatched.
+    pub fn num_mismatched(&self, other: &BitState) -> usize {
+        let mut count = 0;
+        for i in 0..STATE_WORDS {
+            let wrong = (self.values[i] ^ other.values[i]) | !self.mask[i];
+            count += (wrong & other.mask[i]).count_ones() as usize;
+        }
+
+        count
+    }
+
+    /// Overwrite this state with the conditions in <other>.
+    pub fn apply(&self, other: &BitState) -> BitState {
+        let mut new_state = *self;
+        for i in 0..STATE_WORDS {
+            new_state.values[i] = (self.values[i] & !other.mask[i]) | other.values[i];
+            new_state.mask[i] |= other.mask[i];
+        }
+
+        new_state
+    }
+}
+
 #[derive(Clone, Deserialize, Eq)]
 pub struct Action {
     pub name: String,
     pub cost: usize,
     pub pre: State,
     pub post: State,
+    /// Skill used to do this, if any. Scales how much gets done & how much
+    /// energy it takes, see `Skills`.
+    #[serde(default)]
+    pub skill: Option<Skill>,
+    /// Class of tool that has to be equipped to do this, if any. Added to
+    /// the pre-conditions as `Equipped(class)` so plans include going to get
+    /// one.
+    #[serde(default)]
+    pub tool: Option<ToolClass>,
+}
+
+impl Action {
+    /// Name of the thing this action is done near, if any, e.g. "tree" for
+    /// chopping one down.
+    pub fn subject(&self) -> Option<&str> {
+        self.pre
+            .keys()
+            .chain(self.post.keys())
+            .find_map(|condition| match condition {
+                Condition::Near(name) => Some(name.as_str()),
+                _ => None,
+            })
+    }
 }
 
 impl fmt::Debug for Action {
@@ -49,10 +167,42 @@ impl PartialEq for Action {
     }
 }
 
+/// `Action` w/ its pre & post conditions converted into `BitState`s.
+struct CompiledAction {
+    cost: usize,
+    pre: BitState,
+    post: BitState,
+}
+
+/// Format of the actions data file.
+#[derive(Deserialize)]
+struct ActionList {
+    actions: Vec<Action>,
+}
+
 #[derive(Deserialize)]
+#[serde(try_from = "ActionList")]
 pub struct Planner {
     /// List of all actions
     actions: Vec<Action>,
+    /// Compiled version of each action, in the same order as `actions`.
+    compiled: Vec<CompiledAction>,
+    /// Every condition used by an action, indexed by `ConditionId`.
+    conditions: Vec<Condition>,
+    condition_ids: HashMap<Condition, ConditionId>,
+}
+
+impl TryFrom<ActionList> for Planner {
+    type Error = Error;
+
+    fn try_from(list: ActionList) -> Result<Self, Self::Error> {
+        let mut planner = Planner::new();
+        for action in list.actions {
+            planner.push_action(action)?;
+        }
+
+        Ok(planner)
+    }
 }
 
 impl fmt::Debug for Planner {
@@ -72,23 +222,18 @@ impl Planner {
     pub fn new() -> Self {
         Planner {
             actions: Default::default(),
+            compiled: Default::default(),
+            conditions: Default::default(),
+            condition_ids: Default::default(),
         }
     }
 
-    pub fn load(input_path: &str) -> Self {
-        let f = File::open(input_path).expect("Failed opening actions");
-        let planner: Planner = match from_reader(f) {
-            Ok(x) => x,
-            Err(e) => {
-                log::error!("Failed to load Planner: {}", e);
-                std::process::exit(1);
-            }
-        };
-        planner
+    pub fn load<P: AsRef<Path>>(input_path: P) -> Result<Self, Error> {
+        read_ron(input_path.as_ref())
     }
 
-    pub fn heuristic(a: &PlanNode, b: &PlanNode) -> usize {
-        a.num_mismatched(&b.state)
+    pub fn heuristic(a: &PlanNode, b: &BitState) -> usize {
+        a.state.num_mismatched(b)
     }
 
     pub fn add_action(
@@ -97,38 +242,90 @@ impl Planner {
         cost: usize,
         pre: Vec<(Condition, bool)>,
         post: Vec<(Condition, bool)>,
-    ) {
-        let mut action = Action {
+    ) -> Result<(), Error> {
+        self.push_action(Action {
             name,
             cost,
-            pre: State::new(),
-            post: State::new(),
-        };
+            pre: pre.into_iter().collect(),
+            post: post.into_iter().collect(),
+            skill: None,
+            tool: None,
+        })
+    }
 
-        for (atom, value) in pre {
-            action.pre.insert(atom, value);
+    fn push_action(&mut self, mut action: Action) -> Result<(), Error> {
+        if let Some(class) = action.tool {
+            action
+                .pre
+                .insert(Condition::Equipped(class.name().to_string()), true);
         }
 
-        for (atom, value) in post {
-            action.post.insert(atom, value);
+        let mut pre = BitState::default();
+        for (condition, value) in action.pre.iter() {
+            pre.set(self.intern(condition)?, *value);
         }
 
+        let mut post = BitState::default();
+        for (condition, value) in action.post.iter() {
+            post.set(self.intern(condition)?, *value);
+        }
+
+        self.compiled.push(CompiledAction {
+            cost: action.cost,
+            pre,
+            post,
+        });
         self.actions.push(action);
+        Ok(())
+    }
+
+    fn intern(&mut self, condition: &Condition) -> Result<ConditionId, Error> {
+        if let Some(id) = self.condition_ids.get(condition) {
+            return Ok(*id);
+        }
+
+        if self.conditions.len() >= MAX_CONDITIONS {
+            return Err(Error::TooManyConditions(MAX_CONDITIONS));
+        }
+
+        let id = ConditionId(self.conditions.len());
+        self.conditions.push(condition.clone());
+        self.condition_ids.insert(condition.clone(), id);
+        Ok(id)
+    }
+
+    /// Id of <condition>, if it's used by any action.
+    pub fn condition_id(&self, condition: &Condition) -> Option<ConditionId> {
+        self.condition_ids.get(condition).copied()
+    }
+
+    pub fn condition(&self, id: ConditionId) -> &Condition {
+        &self.conditions[id.0]
+    }
+
+    /// Convert <state> into a `BitState`. Conditions that no action uses are
+    /// skipped since they can't affect the plan.
+    pub fn compile_state(&self, state: &State) -> BitState {
+        let mut compiled = BitState::default();
+        for (condition, value) in state.iter() {
+            if let Some(id) = self.condition_id(condition) {
+                compiled.set(id, *value);
+            }
+        }
+
+        compiled
     }
 
-    pub fn next_actions<'a>(&self, plan: &PlanNode) -> Vec<(PlanNode, usize)> {
+    pub fn next_actions(&self, plan: &PlanNode) -> Vec<(PlanNode, usize)> {
         let mut potential = Vec::new();
 
-        for action in self.actions.iter() {
+        for (idx, action) in self.compiled.iter().enumerate() {
             // Does the current state match the pre conditions?
-            if plan.num_mismatched(&action.pre) == 0 {
-                let affects = &action.post;
-                let new_state = plan.apply(affects);
-
+            if plan.state.num_mismatched(&action.pre) == 0 {
                 potential.push((
                     PlanNode {
-                        last_action: Some(&action),
-                        state: new_state.clone(),
+                        last_action: Some(idx),
+                        state: plan.state.apply(&action.post),
                     },
                     action.cost,
                 ));
@@ -139,101 +336,154 @@ impl Planner {
     }
 
     pub fn plan(&self, initial: &State, end: &State) -> Vec<Action> {
-        let start = PlanNode::new(initial);
-        let goal = PlanNode::new(end);
+        // No action can change a condition we don't know about, so the
+        // initial state must already match it.
+        let unknown_mismatch = end.iter().any(|(condition, value)| {
+            self.condition_id(condition).is_none() && initial.get(condition) != Some(value)
+        });
+        if unknown_mismatch {
+            return Vec::new();
+        }
+
+        let start = PlanNode::new(self.compile_state(initial));
+        let goal = self.compile_state(end);
 
-        let (_, plan) = find_path(
+        let (_, plan) = find_path_with(
             start,
-            goal.clone(),
-            |node| Planner::heuristic(&node, &goal),
+            |node| node.state.num_mismatched(&goal) == 0,
+            |node| Planner::heuristic(node, &goal),
             |node| self.next_actions(node),
         );
 
         let mut planned_actions = Vec::new();
         for node in plan.iter() {
-            planned_actions.push(node.last_action.unwrap().clone());
+            planned_actions.push(self.actions[node.last_action.unwrap()].clone());
         }
 
         planned_actions
     }
 }
 
-#[derive(Clone, Eq)]
-pub struct PlanNode<'a> {
-    pub last_action: Option<&'a Action>,
-    pub state: State,
-}
-
-impl fmt::Debug for PlanNode<'_> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "PlanNode<{:} - {:?}>",
-            self.last_action.unwrap().name,
-            self.state
-                .iter()
-                .map(|(key, &value)| {
-                    let string = format!("{:?}", key);
-                    if value {
-                        return string.to_uppercase();
-                    }
-
-                    string
-                })
-                .collect::<Vec<String>>()
-        )
-    }
+/// A node in the plan search. Two nodes are the same if their states match,
+/// regardless of which action got us there.
+#[derive(Clone, Debug)]
+pub struct PlanNode {
+    /// Index of the action used to reach this node.
+    pub last_action: Option<usize>,
+    pub state: BitState,
 }
 
-impl Hash for PlanNode<'_> {
+impl Hash for PlanNode {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        if let Some(action) = self.last_action {
-            action.name.hash(state);
-        }
-
-        for (key, value) in self.state.iter() {
-            key.hash(state);
-            value.hash(state);
-        }
+        self.state.hash(state);
     }
 }
 
-impl PartialEq for PlanNode<'_> {
+impl PartialEq for PlanNode {
     fn eq(&self, other: &Self) -> bool {
-        self.num_mismatched(&other.state) == 0
+        self.state == other.state
     }
 }
 
-impl<'a> PlanNode<'a> {
-    pub fn new(initial_state: &State) -> PlanNode {
+impl Eq for PlanNode {}
+
+impl PlanNode {
+    pub fn new(initial_state: BitState) -> PlanNode {
         PlanNode {
             last_action: None,
-            state: initial_state.clone(),
+            state: initial_state,
         }
     }
+}
 
-    pub fn num_mismatched(&self, state: &State) -> usize {
-        let mut count: usize = 0;
-        for (name, target_value) in state.iter() {
-            if let Some(current_value) = self.state.get(name) {
-                if current_value != target_value {
-                    count += 1
-                }
-            } else {
-                count += 1;
-            }
-        }
-
-        count
+#[cfg(test)]
+mod test {
+    use super::{BitState, Condition, ConditionId, Planner, State};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_bit_state() {
+        let mut a = BitState::default();
+        a.set(ConditionId(0), true);
+        a.set(ConditionId(70), false);
+
+        let mut b = BitState::default();
+        b.set(ConditionId(70), false);
+        b.set(ConditionId(0), true);
+        // Insertion order shouldn't matter.
+        assert_eq!(a, b);
+
+        let mut goal = BitState::default();
+        goal.set(ConditionId(0), true);
+        goal.set(ConditionId(70), true);
+        goal.set(ConditionId(130), false);
+        assert_eq!(a.num_mismatched(&goal), 2);
+
+        let applied = a.apply(&goal);
+        assert_eq!(applied.get(ConditionId(70)), Some(true));
+        assert_eq!(applied.get(ConditionId(130)), Some(false));
+        assert_eq!(applied.get(ConditionId(1)), None);
+        assert_eq!(applied.num_mismatched(&goal), 0);
     }
 
-    pub fn apply(&self, state: &State) -> State {
-        let mut new_state = self.state.clone();
-
-        for (name, value) in state.iter() {
-            new_state.insert(name.clone(), value.clone());
-        }
+    #[test]
+    fn test_load_and_plan() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../resources/data/actions.ron");
+        let planner = Planner::load(path).unwrap();
+
+        let initial = State::new();
+        let mut desired = State::new();
+        desired.insert(Condition::Has("wood".to_string()), true);
+
+        // Grabbing wood that's already lying around beats chopping a tree.
+        let planned = planner.plan(&initial, &desired);
+        let names: Vec<&str> = planned.iter().rev().map(|x| x.name.as_str()).collect();
+        assert_eq!(names, vec!["walk_to_wood", "pickup_wood"]);
+
+        // Chopping down a tree takes an axe.
+        desired.insert(Condition::Destroy("tree".to_string()), true);
+        let planned = planner.plan(&initial, &desired);
+        let names: Vec<&str> = planned.iter().rev().map(|x| x.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "walk_to_axe",
+                "equip_axe",
+                "find_wood",
+                "harvest_wood",
+                "pickup_wood"
+            ]
+        );
+        assert_eq!(planned[1].subject(), Some("tree"));
+        assert_eq!(planned[3].subject(), Some("axe"));
+
+        let mut initial = State::new();
+        initial.insert(Condition::Equipped("axe".to_string()), true);
+        let planned = planner.plan(&initial, &desired);
+        let names: Vec<&str> = planned.iter().rev().map(|x| x.name.as_str()).collect();
+        assert_eq!(names, vec!["find_wood", "harvest_wood", "pickup_wood"]);
+    }
 
-        new_state
+    #[test]
+    fn test_plan_unknown_condition() {
+        let mut planner = Planner::new();
+        planner
+            .add_action(
+                "chop".to_string(),
+                1,
+                vec![],
+                vec![(Condition::Has("wood".to_string()), true)],
+            )
+            .unwrap();
+
+        let mut desired = State::new();
+        desired.insert(Condition::Has("wood".to_string()), true);
+        desired.insert(Condition::Has("gold".to_string()), true);
+        assert!(planner.plan(&State::new(), &desired).is_empty());
+
+        // Already have it, so only need to worry about the wood.
+        let mut initial = State::new();
+        initial.insert(Condition::Has("gold".to_string()), true);
+        assert_eq!(planner.plan(&initial, &desired).len(), 1);
     }
 }
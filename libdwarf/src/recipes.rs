@@ -0,0 +1,84 @@
+///
+/// Recipes for turning raw resources into something more useful at a
+/// workshop, e.g. sawing wood into planks at a carpenter's workshop.
+///
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::{config::read_ron, error::Error};
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Recipe {
+    /// Display name, e.g. "saw planks".
+    pub name: String,
+    /// Type of workshop this needs to be made at, e.g. "carpenter".
+    pub workshop: String,
+    /// Resources used up each time this is made.
+    #[serde(default)]
+    pub inputs: HashMap<String, u32>,
+    /// Resources produced each time this is made.
+    pub outputs: HashMap<String, u32>,
+    /// Amount of work it takes to make this once.
+    pub work: u32,
+    /// Skill used to make this, e.g. "carpentry".
+    #[serde(default)]
+    pub skill: Option<String>,
+}
+
+impl Recipe {
+    /// Does this recipe produce <resource>?
+    pub fn makes(&self, resource: &str) -> bool {
+        self.outputs.contains_key(resource)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RecipeBook {
+    recipes: HashMap<String, Recipe>,
+}
+
+impl RecipeBook {
+    pub fn load<P: AsRef<Path>>(input_path: P) -> Result<Self, Error> {
+        read_ron(input_path.as_ref())
+    }
+
+    pub fn get(&self, name: &str) -> Result<&Recipe, Error> {
+        self.recipes
+            .get(name)
+            .ok_or_else(|| Error::UnknownRecipe(name.to_string()))
+    }
+
+    /// Ids of every recipe, sorted by name.
+    pub fn recipe_ids(&self) -> Vec<&String> {
+        let mut ids: Vec<&String> = self.recipes.keys().collect();
+        ids.sort();
+        ids
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RecipeBook;
+    use crate::config::ResourceConfig;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_load_recipes() {
+        let data_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../resources/data");
+        let book = RecipeBook::load(data_dir.join("recipes.ron")).unwrap();
+        let resources = ResourceConfig::load(data_dir.join("resources.ron")).unwrap();
+
+        // Everything a recipe uses or makes should be a known resource.
+        for id in book.recipe_ids() {
+            let recipe = book.get(id).unwrap();
+            assert!(resources.get(&recipe.workshop).is_ok());
+            for resource in recipe.inputs.keys().chain(recipe.outputs.keys()) {
+                assert!(resources.get(resource).is_ok(), "{} in {}", resource, id);
+            }
+        }
+
+        assert!(book.get("plank").unwrap().makes("plank"));
+        assert!(book.get("gold").is_err());
+    }
+}
@@ -0,0 +1,157 @@
+use serde::Deserialize;
+use std::fmt;
+
+use crate::config::CalendarConfig;
+
+const HOURS_PER_DAY: f64 = 24.0;
+/// Hour the sun comes up & the sim starts at.
+const DAWN: f64 = 6.0;
+/// Hour the sun goes down.
+const DUSK: f64 = 20.0;
+/// Hours it takes to go from dark to full daylight & back.
+const TWILIGHT: f64 = 1.0;
+
+/// Season of the year in the sim, e.g. crops only grow in some of them.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    pub const ALL: [Season; 4] = [
+        Season::Spring,
+        Season::Summer,
+        Season::Autumn,
+        Season::Winter,
+    ];
+}
+
+/// In-game date & time of day, derived from how long the sim has been
+/// running. Advanced every tick by `TimeTickSystem`.
+#[derive(Clone, Debug)]
+pub struct Calendar {
+    /// Days since the sim started, the fractional part is the time of day.
+    days: f64,
+    days_per_season: u32,
+}
+
+impl Calendar {
+    pub fn new(config: &CalendarConfig) -> Self {
+        Calendar {
+            days: DAWN / HOURS_PER_DAY,
+            days_per_season: config.days_per_season.max(1),
+        }
+    }
+
+    /// Move time along by <delta_seconds> of sim time. Picks up any changes
+    /// to the config, e.g. after it's been reloaded.
+    pub fn advance(&mut self, delta_seconds: f32, config: &CalendarConfig) {
+        if config.day_length > 0.0 {
+            self.days += f64::from(delta_seconds / config.day_length);
+        }
+        self.days_per_season = config.days_per_season.max(1);
+    }
+
+    /// How far along the current day is, from 0.0 (midnight) to 1.0.
+    pub fn time_of_day(&self) -> f32 {
+        self.days.fract() as f32
+    }
+
+    pub fn hour(&self) -> u32 {
+        (self.days.fract() * HOURS_PER_DAY) as u32
+    }
+
+    pub fn minute(&self) -> u32 {
+        ((self.days.fract() * HOURS_PER_DAY).fract() * 60.0) as u32
+    }
+
+    /// Day of the current season, starting at 1.
+    pub fn day(&self) -> u32 {
+        (self.days as u32 % self.days_per_season) + 1
+    }
+
+    pub fn season(&self) -> Season {
+        let season = (self.days as u32 / self.days_per_season) % Season::ALL.len() as u32;
+        Season::ALL[season as usize]
+    }
+
+    /// Current year, starting at 1.
+    pub fn year(&self) -> u32 {
+        self.days as u32 / (self.days_per_season * Season::ALL.len() as u32) + 1
+    }
+
+    pub fn is_night(&self) -> bool {
+        let hour = self.days.fract() * HOURS_PER_DAY;
+        !(DAWN..DUSK).contains(&hour)
+    }
+
+    /// How bright it is out, from 0.0 in the dead of night to 1.0 during the
+    /// day, fading in & out around dawn & dusk.
+    pub fn daylight(&self) -> f32 {
+        let hour = self.days.fract() * HOURS_PER_DAY;
+        let sunrise = (hour - DAWN + TWILIGHT) / TWILIGHT;
+        let sunset = (DUSK - hour) / TWILIGHT;
+        sunrise.min(sunset).clamp(0.0, 1.0) as f32
+    }
+}
+
+impl Default for Calendar {
+    fn default() -> Self {
+        Calendar::new(&CalendarConfig::default())
+    }
+}
+
+impl fmt::Display for Calendar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} {}, year {}, {:02}:{:02}",
+            self.season(),
+            self.day(),
+            self.year(),
+            self.hour(),
+            self.minute()
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Calendar, Season};
+    use crate::config::CalendarConfig;
+
+    #[test]
+    fn test_calendar() {
+        let config = CalendarConfig {
+            day_length: 240.0,
+            days_per_season: 2,
+        };
+
+        // Starts off at dawn on the first day of the year.
+        let mut calendar = Calendar::new(&config);
+        assert_eq!(calendar.hour(), 6);
+        assert_eq!(calendar.day(), 1);
+        assert_eq!(calendar.season(), Season::Spring);
+        assert_eq!(calendar.year(), 1);
+        assert!(!calendar.is_night());
+        assert!(calendar.daylight() > 0.9);
+
+        // 10 seconds an hour, 15 hours later it's 21:00.
+        calendar.advance(150.0, &config);
+        assert_eq!(calendar.hour(), 21);
+        assert!(calendar.is_night());
+        assert_eq!(calendar.daylight(), 0.0);
+
+        // Seasons & years roll over.
+        calendar.advance(240.0 * 2.0, &config);
+        assert_eq!(calendar.day(), 1);
+        assert_eq!(calendar.season(), Season::Summer);
+        calendar.advance(240.0 * 6.0, &config);
+        assert_eq!(calendar.season(), Season::Spring);
+        assert_eq!(calendar.year(), 2);
+        assert_eq!(calendar.to_string(), "Spring 1, year 2, 21:00");
+    }
+}
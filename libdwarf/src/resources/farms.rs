@@ -0,0 +1,110 @@
+use core::Point3;
+use std::collections::HashSet;
+
+pub type FarmPlotId = u32;
+
+/// Tiles designated for growing a crop.
+#[derive(Clone, Debug)]
+pub struct FarmPlot {
+    pub id: FarmPlotId,
+    /// Crop to plant here, see `CropLibrary`.
+    pub crop: String,
+    /// Every tile in the plot. Only tiles the crop can grow on are included.
+    pub tiles: Vec<Point3<u32>>,
+    /// Tiles that have been tilled & are ready for planting.
+    pub tilled: HashSet<Point3<u32>>,
+}
+
+impl FarmPlot {
+    pub fn contains(&self, pt: &Point3<u32>) -> bool {
+        self.tiles.contains(pt)
+    }
+
+    pub fn is_tilled(&self, pt: &Point3<u32>) -> bool {
+        self.tilled.contains(pt)
+    }
+}
+
+/// Every farm plot designated by the player.
+#[derive(Debug, Default)]
+pub struct Farms {
+    next_id: FarmPlotId,
+    plots: Vec<FarmPlot>,
+}
+
+impl Farms {
+    /// Designate <tiles> as a farm plot for growing <crop>. Tiles that are
+    /// already part of another plot are left out.
+    pub fn designate<I>(&mut self, crop: &str, tiles: I) -> FarmPlotId
+    where
+        I: IntoIterator<Item = Point3<u32>>,
+    {
+        let tiles = tiles
+            .into_iter()
+            .filter(|pt| self.at(pt).is_none())
+            .collect();
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.plots.push(FarmPlot {
+            id,
+            crop: crop.to_string(),
+            tiles,
+            tilled: HashSet::new(),
+        });
+
+        id
+    }
+
+    pub fn remove(&mut self, id: FarmPlotId) {
+        self.plots.retain(|plot| plot.id != id);
+    }
+
+    pub fn get(&self, id: FarmPlotId) -> Option<&FarmPlot> {
+        self.plots.iter().find(|plot| plot.id == id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &FarmPlot> {
+        self.plots.iter()
+    }
+
+    /// Farm plot covering <pt>, if any.
+    pub fn at(&self, pt: &Point3<u32>) -> Option<&FarmPlot> {
+        self.plots.iter().find(|plot| plot.contains(pt))
+    }
+
+    /// Till the soil at <pt>. Returns false if it's not part of a plot or
+    /// has already been tilled.
+    pub fn till(&mut self, pt: &Point3<u32>) -> bool {
+        match self.plots.iter_mut().find(|plot| plot.contains(pt)) {
+            Some(plot) => plot.tilled.insert(*pt),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Farms;
+    use core::Point3;
+
+    #[test]
+    fn test_designate() {
+        let mut farms = Farms::default();
+        let tiles: Vec<_> = (0..3).map(|x| Point3::new(x, 0, 1)).collect();
+        let id = farms.designate("wheat", tiles.clone());
+        // Tiles can only be part of one plot at a time.
+        let other = farms.designate("turnip", tiles);
+        assert!(farms.get(other).unwrap().tiles.is_empty());
+
+        let pt = Point3::new(1, 0, 1);
+        assert_eq!(farms.at(&pt).unwrap().id, id);
+        assert!(farms.till(&pt));
+        assert!(!farms.till(&pt));
+        assert!(!farms.till(&Point3::new(1, 1, 1)));
+        assert!(farms.get(id).unwrap().is_tilled(&pt));
+
+        farms.remove(id);
+        assert!(farms.at(&pt).is_none());
+    }
+}
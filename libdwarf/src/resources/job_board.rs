@@ -0,0 +1,264 @@
+use core::Point3;
+
+use crate::{
+    components::Labor,
+    trigger::{TriggerPriority, TriggerType},
+};
+
+pub type JobId = u32;
+type WorkerId = u32;
+
+/// Number of done/failed jobs we keep around for debugging.
+const MAX_FINISHED: usize = 10;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum JobState {
+    /// Waiting for a worker to pick it up.
+    Open,
+    /// Assigned to a worker, but they haven't started on it yet.
+    Claimed(WorkerId),
+    InProgress(WorkerId),
+    Done,
+    Failed,
+}
+
+#[derive(Clone, Debug)]
+pub struct JobPosting {
+    pub id: JobId,
+    pub task: TriggerType,
+    pub priority: TriggerPriority,
+    /// Labor a worker needs to have enabled to take this job, if any.
+    pub labor: Option<Labor>,
+    pub state: JobState,
+}
+
+impl JobPosting {
+    /// Entity this job is focused on, if any.
+    pub fn target(&self) -> Option<u32> {
+        match self.task {
+            TriggerType::HarvestResource { target, .. } | TriggerType::Hunt { target } => {
+                Some(target)
+            }
+            _ => None,
+        }
+    }
+
+    /// Where the work needs to happen, if we know ahead of time. Animals move
+    /// around so hunts don't have one.
+    pub fn position(&self) -> Option<Point3<u32>> {
+        match self.task {
+            TriggerType::HarvestResource { position, .. } => Some(position),
+            _ => None,
+        }
+    }
+
+    /// Worker who has claimed or is working on this job.
+    pub fn worker(&self) -> Option<WorkerId> {
+        match self.state {
+            JobState::Claimed(worker) | JobState::InProgress(worker) => Some(worker),
+            _ => None,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        !matches!(self.state, JobState::Done | JobState::Failed)
+    }
+}
+
+/// Jobs posted by the player, waiting to be picked up by a worker.
+#[derive(Default)]
+pub struct JobBoard {
+    next_id: JobId,
+    jobs: Vec<JobPosting>,
+}
+
+impl JobBoard {
+    /// Post a new job. Returns `None` if another active job is already
+    /// going after the same target.
+    pub fn post(
+        &mut self,
+        task: TriggerType,
+        priority: TriggerPriority,
+        labor: Option<Labor>,
+    ) -> Option<JobId> {
+        let id = self.next_id;
+        let posting = JobPosting {
+            id,
+            task,
+            priority,
+            labor,
+            state: JobState::Open,
+        };
+
+        if let Some(target) = posting.target() {
+            if self.targets().any(|other| other == target) {
+                return None;
+            }
+        }
+
+        self.next_id += 1;
+        self.jobs.push(posting);
+        Some(id)
+    }
+
+    pub fn get(&self, id: JobId) -> Option<&JobPosting> {
+        self.jobs.iter().find(|job| job.id == id)
+    }
+
+    fn get_mut(&mut self, id: JobId) -> Option<&mut JobPosting> {
+        self.jobs.iter_mut().find(|job| job.id == id)
+    }
+
+    pub fn jobs(&self) -> &[JobPosting] {
+        &self.jobs
+    }
+
+    /// Open jobs, highest priority first. Jobs w/ the same priority are
+    /// ordered by when they were posted.
+    pub fn open_jobs(&self) -> Vec<&JobPosting> {
+        let mut open: Vec<&JobPosting> = self
+            .jobs
+            .iter()
+            .filter(|job| job.state == JobState::Open)
+            .collect();
+        open.sort_by(|a, b| b.priority.cmp(&a.priority).then(a.id.cmp(&b.id)));
+        open
+    }
+
+    /// Job that <worker> has claimed or is working on, if any.
+    pub fn claimed_by(&self, worker: WorkerId) -> Option<&JobPosting> {
+        self.jobs.iter().find(|job| job.worker() == Some(worker))
+    }
+
+    /// Targets of every active job.
+    pub fn targets(&self) -> impl Iterator<Item = u32> + '_ {
+        self.jobs
+            .iter()
+            .filter(|job| job.is_active())
+            .filter_map(|job| job.target())
+    }
+
+    /// Assign an open job to <worker>. Returns false if the job has already
+    /// been taken.
+    pub fn claim(&mut self, id: JobId, worker: WorkerId) -> bool {
+        match self.get_mut(id) {
+            Some(job) if job.state == JobState::Open => {
+                job.state = JobState::Claimed(worker);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Put a claimed job back up for grabs.
+    pub fn release(&mut self, id: JobId) {
+        if let Some(job) = self.get_mut(id) {
+            if let JobState::Claimed(_) = job.state {
+                job.state = JobState::Open;
+            }
+        }
+    }
+
+    /// Put every job <worker> claimed or started back up for grabs, e.g.
+    /// once they've died.
+    pub fn abandon(&mut self, worker: WorkerId) {
+        for job in self.jobs.iter_mut() {
+            if job.worker() == Some(worker) {
+                job.state = JobState::Open;
+            }
+        }
+    }
+
+    pub fn start(&mut self, id: JobId) {
+        if let Some(job) = self.get_mut(id) {
+            if let JobState::Claimed(worker) = job.state {
+                job.state = JobState::InProgress(worker);
+            }
+        }
+    }
+
+    pub fn finish(&mut self, id: JobId, success: bool) {
+        if let Some(job) = self.get_mut(id) {
+            job.state = if success {
+                JobState::Done
+            } else {
+                JobState::Failed
+            };
+        }
+
+        // Drop the oldest finished jobs.
+        let num_finished = self.jobs.iter().filter(|job| !job.is_active()).count();
+        let mut to_remove = num_finished.saturating_sub(MAX_FINISHED);
+        self.jobs.retain(|job| {
+            if to_remove > 0 && !job.is_active() {
+                to_remove -= 1;
+                return false;
+            }
+
+            true
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{JobBoard, JobState};
+    use crate::trigger::{TriggerPriority, TriggerType};
+    use core::Point3;
+
+    fn harvest(target: u32) -> TriggerType {
+        TriggerType::HarvestResource {
+            target,
+            position: Point3::new(0, 0, 0),
+            resource: "wood".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_job_board() {
+        let mut board = JobBoard::default();
+        let low = board.post(harvest(1), TriggerPriority::LOW, None).unwrap();
+        let high = board.post(harvest(2), TriggerPriority::HIGH, None).unwrap();
+        // Already have a job for this target.
+        assert!(board
+            .post(harvest(1), TriggerPriority::HIGH, None)
+            .is_none());
+
+        let open: Vec<u32> = board.open_jobs().iter().map(|job| job.id).collect();
+        assert_eq!(open, vec![high, low]);
+
+        assert!(board.claim(high, 10));
+        assert!(!board.claim(high, 11));
+        assert_eq!(board.claimed_by(10).unwrap().id, high);
+
+        board.release(high);
+        assert_eq!(board.get(high).unwrap().state, JobState::Open);
+
+        assert!(board.claim(high, 11));
+        board.start(high);
+        assert_eq!(board.get(high).unwrap().state, JobState::InProgress(11));
+        board.abandon(11);
+        assert_eq!(board.get(high).unwrap().state, JobState::Open);
+
+        assert!(board.claim(high, 11));
+        board.finish(high, true);
+        assert_eq!(board.get(high).unwrap().state, JobState::Done);
+        assert!(board.claimed_by(11).is_none());
+    }
+
+    #[test]
+    fn test_hunt_job() {
+        let mut board = JobBoard::default();
+        let hunt = TriggerType::Hunt { target: 3 };
+        let id = board
+            .post(hunt.clone(), TriggerPriority::MEDIUM, None)
+            .unwrap();
+        // Only one hunting party per animal.
+        assert!(board.post(hunt, TriggerPriority::HIGH, None).is_none());
+
+        // Animals move around, so there's no telling where the work happens.
+        let job = board.get(id).unwrap();
+        assert_eq!(job.target(), Some(3));
+        assert_eq!(job.position(), None);
+    }
+}
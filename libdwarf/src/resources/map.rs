@@ -7,17 +7,84 @@ use std::collections::HashMap;
 use crate::{
     components::{EntityInfo, MapObject},
     config::ResourceConfig,
+    crops::CropType,
+    error::Error,
+    resources::{FarmPlotId, Farms, Stockpiles, Traffic},
 };
 
 use core::Point3;
 use libpath::find_path;
 use libterrain::{Biome, Object, Path, TerrainChunk};
 
+/// Number of ticks a worker waits on someone before looking for a way around
+/// them. If there isn't one, they keep waiting on the path they had.
+const REPATH_AFTER: u32 = 3;
+/// Number of ticks a worker waits on someone before giving up on their path.
+const GIVE_UP_AFTER: u32 = 12;
+/// Extra cost of walking through a tile buried in snow.
+const SNOW_MOVE_COST: f32 = 2.0;
+
+/// Outcome of a worker trying to take a step along their path.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Movement {
+    /// Took a step, or there was nowhere left to go.
+    Moved,
+    /// Someone is in the way, waiting for them to move.
+    Waiting,
+    /// There's no way to get to the end of the path anymore.
+    Stuck,
+}
+
+/// Everything sitting on a single tile. A tile can hold one solid object,
+/// e.g. a tree, w/ any number of items piled up around it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ObjectStack {
+    /// Object that blocks movement through this tile.
+    pub solid: Option<u32>,
+    /// Items lying on this tile, most recently dropped last. Crops can be
+    /// walked over too, so they end up here as well.
+    pub items: Vec<u32>,
+}
+
+impl ObjectStack {
+    /// Every object on this tile, starting w/ the one on top.
+    pub fn iter(&self) -> impl Iterator<Item = &u32> {
+        self.solid.iter().chain(self.items.iter().rev())
+    }
+
+    pub fn top(&self) -> Option<u32> {
+        self.iter().next().copied()
+    }
+
+    pub fn contains(&self, entity: u32) -> bool {
+        self.iter().any(|id| *id == entity)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.solid.is_none() && self.items.is_empty()
+    }
+}
+
 pub struct Map {
-    // TODO: Support multiple objects per tile.
-    pub object_map: HashMap<Point3<u32>, u32>,
-    /// Location map of all the workers.
-    pub worker_map: HashMap<Point3<u32>, u32>,
+    /// Location map of all the objects.
+    pub object_map: HashMap<Point3<u32>, ObjectStack>,
+    /// Location map of all the workers. Workers can end up sharing a tile,
+    /// e.g. when spawned on top of each other.
+    pub worker_map: HashMap<Point3<u32>, Vec<u32>>,
+    /// Creatures roaming the map, only one can stand on a tile at a time.
+    pub creature_map: HashMap<Point3<u32>, u32>,
+    /// Tiles workers are about to walk into.
+    pub traffic: Traffic,
+    /// Zones designated for storing items.
+    pub stockpiles: Stockpiles,
+    /// Zones designated for growing crops.
+    pub farms: Farms,
+    /// Tiles where workers can get a drink, i.e. on or right next to water.
+    pub water: Vec<Point3<u32>>,
+    /// Puddles left behind by the rain, dry up once it stops raining.
+    pub puddles: Vec<Point3<u32>>,
+    /// How deep the snow outside is, from 0.0 (none) to 1.0.
+    pub snow_cover: f32,
     pub terrain: TerrainChunk,
     // World dimensions
     pub width: u32,
@@ -25,22 +92,21 @@ pub struct Map {
 }
 
 impl Map {
-    pub fn initialize(world: &mut World, terrain: &TerrainChunk, width: u32, height: u32) -> Self {
-        let mut object_map = HashMap::new();
+    pub fn initialize(
+        world: &mut World,
+        terrain: &TerrainChunk,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, Error> {
+        let mut object_map: HashMap<Point3<u32>, ObjectStack> = HashMap::new();
 
-        let resource_map = {
-            let resources = world.read_resource::<ResourceConfig>();
-            resources.map.clone()
-        };
+        let resources = (*world.read_resource::<ResourceConfig>()).clone();
 
         // Initialize map w/ objects created in terrain gen
         for (pos, object) in &terrain.objects {
             let mut entity_builder = world.create_entity();
             entity_builder = match object {
-                Object::TREE => {
-                    let resource = resource_map.get("tree").unwrap().clone();
-                    entity_builder.with(MapObject::new(&resource))
-                }
+                Object::TREE => entity_builder.with(MapObject::new(resources.get("tree")?)),
             };
 
             entity_builder = entity_builder.with(EntityInfo {
@@ -49,16 +115,23 @@ impl Map {
             });
 
             let entity = entity_builder.build();
-            object_map.insert(*pos, entity.id());
+            object_map.entry(*pos).or_default().solid = Some(entity.id());
         }
 
-        Map {
+        Ok(Map {
             object_map,
             worker_map: HashMap::new(),
+            creature_map: HashMap::new(),
+            traffic: Traffic::default(),
+            stockpiles: Stockpiles::default(),
+            farms: Farms::default(),
+            water: find_water(terrain),
+            puddles: Vec::new(),
+            snow_cover: 0.0,
             terrain: terrain.clone(),
             width,
             height,
-        }
+        })
     }
 
     pub fn is_inside_map(&self, pt: Point3<i32>) -> bool {
@@ -87,44 +160,282 @@ impl Map {
         // Find the neighbors and return the results
         let mut results = Vec::new();
         for idx in &neighbor_idx {
-            if let Some(oid) = self.object_map.get(idx) {
-                results.push(oid);
+            if let Some(stack) = self.object_map.get(idx) {
+                results.extend(stack.iter());
             }
         }
         results
     }
 
-    pub fn find_path(&self, start: &Point3<u32>, end: &Point3<u32>) -> Path {
+    /// Find a path from <start> to <end> that goes around anything in the
+    /// way. <end> itself can be occupied, e.g. when walking up to a tree.
+    /// Returns `None` if there's no way to reach <end>.
+    pub fn find_path(&self, start: &Point3<u32>, end: &Point3<u32>) -> Option<Path> {
         let (_, path) = find_path(
             *start,
             *end,
-            |node| TerrainChunk::heuristic(&node, start),
-            |pt| self.terrain.neighbors(pt),
+            |node| TerrainChunk::heuristic(node, end),
+            |pt| {
+                self.terrain
+                    .neighbors(pt)
+                    .into_iter()
+                    .filter(|(pt, _)| pt == end || !self.is_blocked(pt))
+                    .map(|(pt, _)| (pt, self.move_cost(&pt)))
+                    .collect::<Vec<_>>()
+            },
+        );
+
+        if path.is_empty() && start != end {
+            return None;
+        }
+
+        Some(path)
+    }
+
+    /// Is each step in <path> walkable & free, starting from <start>? Paths
+    /// are stored in reverse, so the first step is at the end.
+    pub fn is_valid_path(&self, start: &Point3<u32>, path: &[Point3<u32>]) -> bool {
+        let mut current = *start;
+        for next in path.iter().rev() {
+            let is_neighbor = self
+                .terrain
+                .neighbors(&current)
+                .iter()
+                .any(|(pt, _)| pt == next);
+
+            if !is_neighbor || self.is_blocked(next) {
+                return false;
+            }
+
+            current = *next;
+        }
+
+        true
+    }
+
+    /// Spread <count> items out over <center> & the free tiles around it,
+    /// piling them up once every tile has something on it.
+    pub fn scatter(&self, center: &Point3<u32>, count: usize) -> Vec<Point3<u32>> {
+        let mut spots = vec![*center];
+        spots.extend(
+            self.terrain
+                .neighbors(center)
+                .into_iter()
+                .map(|(pt, _)| pt)
+                .filter(|pt| pt.z == center.z && !self.is_solid(pt)),
         );
 
-        path
+        spots.iter().cycle().take(count).copied().collect()
+    }
+
+    /// Is there anything at <pt> that would get in the way of a worker?
+    pub fn is_blocked(&self, pt: &Point3<u32>) -> bool {
+        self.is_solid(pt) || self.worker_map.contains_key(pt) || self.creature_map.contains_key(pt)
+    }
+
+    /// Is there something at <pt> that won't move out of the way on its own?
+    pub fn is_solid(&self, pt: &Point3<u32>) -> bool {
+        self.object_map
+            .get(pt)
+            .map(|stack| stack.solid.is_some())
+            .unwrap_or(false)
+    }
+
+    /// Move <worker> one step along <path>, waiting or stepping aside if
+    /// someone else is in the way. Paths are stored in reverse, so the next
+    /// step is at the end & the destination at the start.
+    pub fn step_worker(&mut self, worker: u32, pos: &mut Point3<u32>, path: &mut Path) -> Movement {
+        let (next, destination) = match (path.last(), path.first()) {
+            (Some(next), Some(destination)) => (*next, *destination),
+            _ => return Movement::Moved,
+        };
+
+        // Something got put in the way, look for a way around it.
+        if self.is_solid(&next) {
+            if next == destination {
+                return Movement::Stuck;
+            }
+
+            return match self.find_path(pos, &destination) {
+                Some(new_path) => {
+                    *path = new_path;
+                    Movement::Waiting
+                }
+                None => Movement::Stuck,
+            };
+        }
+
+        let blocker = self
+            .workers_at(&next)
+            .iter()
+            .copied()
+            .find(|other| *other != worker)
+            .or_else(|| self.creature_at(&next))
+            .or_else(|| {
+                self.traffic
+                    .reserved_by(&next)
+                    .filter(|other| *other != worker)
+            });
+
+        let other = match blocker {
+            Some(other) => other,
+            None => {
+                path.pop();
+                self.move_worker(worker, *pos, next);
+                *pos = next;
+                self.traffic.stop_waiting(worker);
+                self.traffic.reserve(worker, path);
+                return Movement::Moved;
+            }
+        };
+
+        let ticks = self.traffic.wait(worker);
+
+        // Two workers trying to get past each other. The one w/ the higher id
+        // steps aside & comes back once the other is through. W/ nowhere to
+        // step aside, e.g. in a corridor one tile wide, they back up into the
+        // tiles the other worker is heading for until there's room.
+        let head_on = self.traffic.reserved_by(pos) == Some(other);
+        if head_on && worker > other {
+            let aside = self
+                .free_neighbor(worker, pos, &next)
+                .or_else(|| self.free_neighbor(other, pos, &next));
+            if let Some(aside) = aside {
+                path.push(*pos);
+                self.move_worker(worker, *pos, aside);
+                *pos = aside;
+                self.traffic.clear(worker);
+                return Movement::Moved;
+            }
+        }
+
+        if ticks >= GIVE_UP_AFTER {
+            return Movement::Stuck;
+        }
+
+        if ticks == REPATH_AFTER {
+            if let Some(new_path) = self.find_path(pos, &destination) {
+                *path = new_path;
+            }
+        }
+
+        Movement::Waiting
+    }
+
+    /// Free tile next to <pt> that <worker> could step aside into, other
+    /// than <avoid>. Tiles <worker> has reserved count as free.
+    fn free_neighbor(
+        &self,
+        worker: u32,
+        pt: &Point3<u32>,
+        avoid: &Point3<u32>,
+    ) -> Option<Point3<u32>> {
+        self.terrain
+            .neighbors(pt)
+            .into_iter()
+            .map(|(neighbor, _)| neighbor)
+            .find(|neighbor| {
+                neighbor != avoid
+                    && !self.is_blocked(neighbor)
+                    && self
+                        .traffic
+                        .reserved_by(neighbor)
+                        .map(|other| other == worker)
+                        .unwrap_or(true)
+            })
     }
 
     pub fn has_collision(&self, pt: Point3<i32>) -> bool {
         if self.is_inside_map(pt) {
             let key = Point3::new(pt.x as u32, pt.y as u32, pt.z as u32);
-            return self.object_map.contains_key(&key) || self.worker_map.contains_key(&key);
+            return self.is_blocked(&key);
         }
 
         false
     }
 
+    /// Object on top of the stack at <pt>, if any.
     pub fn objects_at(&self, pt: Point3<i32>) -> Option<u32> {
         if self.is_inside_map(pt) {
             let key = Point3::new(pt.x as u32, pt.y as u32, pt.z as u32);
-            if let Some(id) = self.object_map.get(&key) {
-                return Some(*id);
-            }
+            return self.stack_at(&key).and_then(|stack| stack.top());
         }
 
         None
     }
 
+    pub fn stack_at(&self, pt: &Point3<u32>) -> Option<&ObjectStack> {
+        self.object_map.get(pt)
+    }
+
+    /// Biome of the ground a worker standing on <pt> would be standing on.
+    pub fn ground_at(&self, pt: &Point3<u32>) -> Option<Biome> {
+        if pt.z == 0 {
+            return None;
+        }
+
+        self.terrain.get(pt.x, pt.y, pt.z - 1)
+    }
+
+    /// Highest tile a worker could stand on at (<x>, <y>).
+    pub fn surface_at(&self, x: u32, y: u32) -> Option<Point3<u32>> {
+        (0..self.terrain.depth())
+            .rev()
+            .map(|z| Point3::new(x, y, z))
+            .find(|pt| self.terrain.is_walkable(pt))
+    }
+
+    /// Is there anything overhead at <pt> to keep the weather off?
+    pub fn is_sheltered(&self, pt: &Point3<u32>) -> bool {
+        (pt.z + 1..self.terrain.depth()).any(|z| self.terrain.get(pt.x, pt.y, z).is_some())
+    }
+
+    /// Cost of walking into <pt>, higher when it's buried in snow.
+    pub fn move_cost(&self, pt: &Point3<u32>) -> usize {
+        if self.snow_cover <= 0.0 || self.is_sheltered(pt) {
+            return 1;
+        }
+
+        1 + (self.snow_cover * SNOW_MOVE_COST).round() as usize
+    }
+
+    /// Every tile where a worker can get a drink, including any puddles.
+    pub fn water_sources(&self) -> impl Iterator<Item = &Point3<u32>> {
+        self.water.iter().chain(self.puddles.iter())
+    }
+
+    /// Closest tile to <from> where a worker can get a drink.
+    pub fn nearest_water(&self, from: &Point3<u32>) -> Option<Point3<u32>> {
+        self.water_sources()
+            .filter(|pt| self.terrain.is_walkable(pt))
+            .min_by_key(|pt| TerrainChunk::heuristic(from, pt))
+            .copied()
+    }
+
+    /// Designate the rectangle between <a> & <b> as a farm plot for growing
+    /// <crop>, skipping any tiles it can't be planted on.
+    pub fn designate_farm(
+        &mut self,
+        a: Point3<u32>,
+        b: Point3<u32>,
+        crop_id: &str,
+        crop: &CropType,
+    ) -> FarmPlotId {
+        let mut tiles = Vec::new();
+        for y in a.y.min(b.y)..=a.y.max(b.y).min(self.height - 1) {
+            for x in a.x.min(b.x)..=a.x.max(b.x).min(self.width - 1) {
+                let pt = Point3::new(x, y, a.z);
+                let is_suitable =
+                    matches!(self.ground_at(&pt), Some(biome) if crop.grows_on(&biome));
+                if is_suitable && self.terrain.is_walkable(&pt) {
+                    tiles.push(pt);
+                }
+            }
+        }
+
+        self.farms.designate(crop_id, tiles)
+    }
+
     pub fn terrain_at(&self, pt: Point3<i32>) -> Option<Biome> {
         if self.is_inside_map(pt) {
             self.terrain.get(pt.x as u32, pt.y as u32, pt.z as u32)
@@ -136,28 +447,255 @@ impl Map {
     pub fn worker_at(&self, pt: Point3<i32>) -> Option<u32> {
         if self.is_inside_map(pt) {
             let key = Point3::new(pt.x as u32, pt.y as u32, pt.z as u32);
-            if let Some(id) = self.worker_map.get(&key) {
-                return Some(*id);
-            }
+            return self.workers_at(&key).first().copied();
         }
 
         None
     }
 
+    /// Every worker standing on <pt>.
+    pub fn workers_at(&self, pt: &Point3<u32>) -> &[u32] {
+        self.worker_map
+            .get(pt)
+            .map(|workers| workers.as_slice())
+            .unwrap_or(&[])
+    }
+
     pub fn move_worker(&mut self, entity: u32, old_pt: Point3<u32>, new_pt: Point3<u32>) {
-        self.worker_map.remove(&old_pt);
+        self.remove_worker(entity, old_pt);
         self.track_worker(entity, new_pt);
     }
 
-    pub fn remove_object(&mut self, _entity: u32, pt: Point3<u32>) {
-        self.object_map.remove(&pt);
+    /// Stop tracking <entity> at <pt>, e.g. once it's moved away or died.
+    pub fn remove_worker(&mut self, entity: u32, pt: Point3<u32>) {
+        if let Some(workers) = self.worker_map.get_mut(&pt) {
+            workers.retain(|worker| *worker != entity);
+            if workers.is_empty() {
+                self.worker_map.remove(&pt);
+            }
+        }
     }
 
-    pub fn track_object(&mut self, entity: u32, pt: Point3<u32>) {
-        self.object_map.insert(pt, entity);
+    pub fn remove_object(&mut self, entity: u32, pt: Point3<u32>) {
+        if let Some(stack) = self.object_map.get_mut(&pt) {
+            if stack.solid == Some(entity) {
+                stack.solid = None;
+            }
+            stack.items.retain(|item| *item != entity);
+
+            if stack.is_empty() {
+                self.object_map.remove(&pt);
+            }
+        }
+    }
+
+    /// Place an object on <pt>. Items pile up on top of each other, but
+    /// there can only be one solid object per tile. Returns false if the
+    /// object doesn't fit.
+    pub fn track_object(&mut self, entity: u32, pt: Point3<u32>, is_item: bool) -> bool {
+        let stack = self.object_map.entry(pt).or_default();
+        if is_item {
+            stack.items.push(entity);
+        } else if stack.solid.is_none() {
+            stack.solid = Some(entity);
+        } else {
+            return false;
+        }
+
+        true
     }
 
     pub fn track_worker(&mut self, entity: u32, pt: Point3<u32>) {
-        self.worker_map.insert(pt, entity);
+        let workers = self.worker_map.entry(pt).or_default();
+        if !workers.contains(&entity) {
+            workers.push(entity);
+        }
+    }
+
+    /// Creature standing on <pt>, if any.
+    pub fn creature_at(&self, pt: &Point3<u32>) -> Option<u32> {
+        self.creature_map.get(pt).copied()
+    }
+
+    pub fn track_creature(&mut self, entity: u32, pt: Point3<u32>) {
+        self.creature_map.insert(pt, entity);
+    }
+
+    pub fn move_creature(&mut self, entity: u32, old_pt: Point3<u32>, new_pt: Point3<u32>) {
+        self.remove_creature(entity, old_pt);
+        self.track_creature(entity, new_pt);
+    }
+
+    /// Stop tracking <entity> at <pt>, e.g. once it's died.
+    pub fn remove_creature(&mut self, entity: u32, pt: Point3<u32>) {
+        if self.creature_at(&pt) == Some(entity) {
+            self.creature_map.remove(&pt);
+        }
+    }
+}
+
+/// Walkable tiles that are on or right next to water.
+fn find_water(terrain: &TerrainChunk) -> Vec<Point3<u32>> {
+    let is_water = |x: i64, y: i64, z: u32| {
+        x >= 0
+            && y >= 0
+            && x < i64::from(terrain.width)
+            && y < i64::from(terrain.height)
+            && matches!(terrain.get(x as u32, y as u32, z), Some(Biome::OCEAN))
+    };
+
+    let mut water = Vec::new();
+    for z in 1..terrain.depth() {
+        for y in 0..terrain.height {
+            for x in 0..terrain.width {
+                let pt = Point3::new(x, y, z);
+                if !terrain.is_walkable(&pt) {
+                    continue;
+                }
+
+                let (x, y) = (i64::from(x), i64::from(y));
+                let near_water = [(0, 0), (-1, 0), (1, 0), (0, -1), (0, 1)]
+                    .iter()
+                    .any(|(dx, dy)| is_water(x + dx, y + dy, z - 1));
+                if near_water {
+                    water.push(pt);
+                }
+            }
+        }
+    }
+
+    water
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Map, Movement};
+    use crate::resources::{Farms, Stockpiles, Traffic};
+    use core::Point3;
+    use libterrain::TerrainChunk;
+    use std::collections::HashMap;
+
+    fn empty_map(width: u32, height: u32) -> Map {
+        Map {
+            object_map: HashMap::new(),
+            worker_map: HashMap::new(),
+            creature_map: HashMap::new(),
+            traffic: Traffic::default(),
+            stockpiles: Stockpiles::default(),
+            farms: Farms::default(),
+            water: Vec::new(),
+            puddles: Vec::new(),
+            snow_cover: 0.0,
+            terrain: TerrainChunk::new(width, height),
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn test_workers_pass_each_other() {
+        let mut map = empty_map(4, 4);
+        let (mut a, mut b) = (Point3::new(0, 0, 0), Point3::new(3, 0, 0));
+        map.track_worker(1, a);
+        map.track_worker(2, b);
+
+        // Both workers want to walk straight through each other.
+        let mut path_a = vec![b, Point3::new(2, 0, 0), Point3::new(1, 0, 0)];
+        let mut path_b = vec![a, Point3::new(1, 0, 0), Point3::new(2, 0, 0)];
+        map.traffic.reserve(1, &path_a);
+        map.traffic.reserve(2, &path_b);
+
+        for _ in 0..20 {
+            assert_ne!(map.step_worker(1, &mut a, &mut path_a), Movement::Stuck);
+            assert_ne!(map.step_worker(2, &mut b, &mut path_b), Movement::Stuck);
+            assert_ne!(a, b);
+            if path_a.is_empty() && path_b.is_empty() {
+                break;
+            }
+        }
+
+        assert_eq!(a, Point3::new(3, 0, 0));
+        assert_eq!(b, Point3::new(0, 0, 0));
+        assert_eq!(map.workers_at(&a), &[1]);
+        assert_eq!(map.workers_at(&b), &[2]);
+    }
+
+    #[test]
+    fn test_workers_swap_through_corridor() {
+        // Two rooms joined by a corridor only one tile wide.
+        let mut map = empty_map(9, 3);
+        for (id, x) in (2..7).enumerate() {
+            map.track_object(100 + id as u32 * 2, Point3::new(x, 0, 0), false);
+            map.track_object(101 + id as u32 * 2, Point3::new(x, 2, 0), false);
+        }
+
+        let (start_a, start_b) = (Point3::new(0, 1, 0), Point3::new(8, 1, 0));
+        let (mut a, mut b) = (start_a, start_b);
+        map.track_worker(1, a);
+        map.track_worker(2, b);
+
+        let mut path_a = map.find_path(&a, &start_b).unwrap();
+        let mut path_b = map.find_path(&b, &start_a).unwrap();
+        map.traffic.reserve(1, &path_a);
+        map.traffic.reserve(2, &path_b);
+
+        for _ in 0..40 {
+            assert_ne!(map.step_worker(1, &mut a, &mut path_a), Movement::Stuck);
+            assert_ne!(map.step_worker(2, &mut b, &mut path_b), Movement::Stuck);
+            assert_ne!(a, b);
+            if path_a.is_empty() && path_b.is_empty() {
+                break;
+            }
+        }
+
+        assert_eq!(a, start_b);
+        assert_eq!(b, start_a);
+        assert_eq!(map.workers_at(&a), &[1]);
+        assert_eq!(map.workers_at(&b), &[2]);
+    }
+
+    #[test]
+    fn test_creatures_take_up_space() {
+        let mut map = empty_map(4, 1);
+        let (start, end) = (Point3::new(0, 0, 0), Point3::new(3, 0, 0));
+        map.track_creature(5, Point3::new(1, 0, 0));
+        assert!(map.is_blocked(&Point3::new(1, 0, 0)));
+        assert!(map.find_path(&start, &end).is_none());
+
+        map.move_creature(5, Point3::new(1, 0, 0), end);
+        assert_eq!(map.creature_at(&end), Some(5));
+        assert!(map.find_path(&start, &Point3::new(2, 0, 0)).is_some());
+
+        map.remove_creature(5, end);
+        assert!(!map.is_blocked(&end));
+    }
+
+    #[test]
+    fn test_object_stacks() {
+        let mut map = empty_map(4, 4);
+        let pt = Point3::new(1, 1, 0);
+
+        // Tree w/ a pile of wood dropped around it.
+        assert!(map.track_object(1, pt, false));
+        for wood in 2..5 {
+            assert!(map.track_object(wood, pt, true));
+        }
+        // Only one tree per tile.
+        assert!(!map.track_object(5, pt, false));
+
+        let stack = map.stack_at(&pt).unwrap();
+        assert_eq!(stack.iter().copied().collect::<Vec<_>>(), vec![1, 4, 3, 2]);
+        assert_eq!(map.find_neighbors(Point3::new(1, 0, 0)).len(), 4);
+        assert!(map.is_solid(&pt));
+
+        // Once the tree is gone, the wood can be walked over.
+        map.remove_object(1, pt);
+        assert!(!map.is_solid(&pt));
+        assert_eq!(map.objects_at(Point3::new(1, 1, 0)), Some(4));
+
+        for wood in 2..5 {
+            map.remove_object(wood, pt);
+        }
+        assert!(map.stack_at(&pt).is_none());
     }
 }
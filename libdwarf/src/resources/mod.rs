@@ -1,6 +1,22 @@
+mod calendar;
+mod farms;
+mod job_board;
 mod map;
+mod production;
+mod rng;
+mod stockpiles;
 mod task_queue;
 pub mod time;
+mod traffic;
+mod weather;
 
+pub use calendar::*;
+pub use farms::*;
+pub use job_board::*;
 pub use map::*;
+pub use production::*;
+pub use rng::*;
+pub use stockpiles::*;
 pub use task_queue::*;
+pub use traffic::*;
+pub use weather::*;
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+
+pub type OrderId = u32;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OrderKind {
+    /// Make the recipe this many more times.
+    Make(u32),
+    /// Keep making the recipe until the colony has at least this many of
+    /// each output.
+    KeepStocked(u32),
+}
+
+#[derive(Clone, Debug)]
+pub struct ProductionOrder {
+    pub id: OrderId,
+    /// Recipe to make, see `RecipeBook`.
+    pub recipe: String,
+    pub kind: OrderKind,
+}
+
+impl ProductionOrder {
+    /// Does this order still need work, given the colony-wide <stock> of
+    /// each resource the recipe makes?
+    pub fn is_pending<I>(&self, stock: I) -> bool
+    where
+        I: IntoIterator<Item = u32>,
+    {
+        match self.kind {
+            OrderKind::Make(count) => count > 0,
+            OrderKind::KeepStocked(target) => stock.into_iter().any(|count| count < target),
+        }
+    }
+}
+
+/// Production orders queued up by the player, in the order they were given.
+#[derive(Debug, Default)]
+pub struct ProductionQueue {
+    next_id: OrderId,
+    orders: Vec<ProductionOrder>,
+}
+
+impl ProductionQueue {
+    pub fn add(&mut self, recipe: &str, kind: OrderKind) -> OrderId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.orders.push(ProductionOrder {
+            id,
+            recipe: recipe.to_string(),
+            kind,
+        });
+
+        id
+    }
+
+    pub fn remove(&mut self, id: OrderId) {
+        self.orders.retain(|order| order.id != id);
+    }
+
+    pub fn orders(&self) -> &[ProductionOrder] {
+        &self.orders
+    }
+
+    /// Orders that still need work, given the colony-wide <stock> &
+    /// <outputs> of each recipe.
+    pub fn pending<'a>(
+        &'a self,
+        stock: &'a HashMap<String, u32>,
+        outputs: impl Fn(&str) -> Vec<String> + 'a,
+    ) -> impl Iterator<Item = &'a ProductionOrder> + 'a {
+        self.orders.iter().filter(move |order| {
+            let counts = outputs(&order.recipe)
+                .into_iter()
+                .map(|resource| stock.get(&resource).copied().unwrap_or(0));
+            order.is_pending(counts)
+        })
+    }
+
+    /// <recipe> was made once, count it towards the first order for it.
+    /// Orders for a set number of items are removed once they're done.
+    pub fn made(&mut self, recipe: &str) {
+        let order = self
+            .orders
+            .iter_mut()
+            .find(|order| order.recipe == recipe && !matches!(order.kind, OrderKind::Make(0)));
+
+        if let Some(ProductionOrder {
+            kind: OrderKind::Make(count),
+            ..
+        }) = order
+        {
+            *count = count.saturating_sub(1);
+        }
+
+        self.orders
+            .retain(|order| !matches!(order.kind, OrderKind::Make(0)));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{OrderKind, ProductionQueue};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_production_orders() {
+        let mut queue = ProductionQueue::default();
+        queue.add("plank", OrderKind::Make(2));
+        let stocked = queue.add("plank", OrderKind::KeepStocked(10));
+
+        let outputs = |_: &str| vec!["plank".to_string()];
+        let mut stock = HashMap::new();
+        stock.insert("plank".to_string(), 10);
+        assert_eq!(queue.pending(&stock, outputs).count(), 1);
+
+        queue.made("plank");
+        queue.made("plank");
+        assert_eq!(queue.pending(&stock, outputs).count(), 0);
+        assert_eq!(queue.orders().len(), 1);
+
+        // Running low again.
+        stock.insert("plank".to_string(), 4);
+        assert_eq!(queue.pending(&stock, outputs).next().unwrap().id, stocked);
+    }
+}
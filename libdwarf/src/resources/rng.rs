@@ -0,0 +1,47 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Random number generator used by the sim. Seeded so that a run can be
+/// replayed, e.g. when tracking down a bug.
+pub struct SimRng {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl SimRng {
+    pub fn new(seed: u64) -> Self {
+        SimRng {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Random number between <min> & <max>, inclusive.
+    pub fn range(&mut self, min: u32, max: u32) -> u32 {
+        if max <= min {
+            return min;
+        }
+
+        self.rng.gen_range(min, max + 1)
+    }
+
+    /// Returns true w/ a probability of <chance> (0.0 - 1.0).
+    pub fn chance(&mut self, chance: f32) -> bool {
+        if chance >= 1.0 {
+            return true;
+        } else if chance <= 0.0 {
+            return false;
+        }
+
+        self.rng.gen::<f32>() < chance
+    }
+}
+
+impl Default for SimRng {
+    fn default() -> Self {
+        SimRng::new(rand::random())
+    }
+}
@@ -0,0 +1,134 @@
+use core::Point3;
+use std::collections::{HashMap, HashSet};
+
+pub type StockpileId = u32;
+
+/// Max number of items piled up on a single stockpile tile.
+pub const ITEMS_PER_TILE: usize = 5;
+
+/// Rectangle of tiles where workers bring items to be stored.
+#[derive(Clone, Debug)]
+pub struct Stockpile {
+    pub id: StockpileId,
+    /// Opposite corners of the stockpile, inclusive. Both corners are on the
+    /// same z-level.
+    pub min: Point3<u32>,
+    pub max: Point3<u32>,
+    /// Resource types that can be stored here. Anything goes if empty.
+    pub accepts: HashSet<String>,
+}
+
+impl Stockpile {
+    pub fn contains(&self, pt: &Point3<u32>) -> bool {
+        pt.x >= self.min.x
+            && pt.x <= self.max.x
+            && pt.y >= self.min.y
+            && pt.y <= self.max.y
+            && pt.z == self.min.z
+    }
+
+    pub fn accepts(&self, resource: &str) -> bool {
+        self.accepts.is_empty() || self.accepts.contains(resource)
+    }
+
+    pub fn tiles(&self) -> impl Iterator<Item = Point3<u32>> + '_ {
+        (self.min.y..=self.max.y).flat_map(move |y| {
+            (self.min.x..=self.max.x).map(move |x| Point3::new(x, y, self.min.z))
+        })
+    }
+}
+
+/// Every stockpile designated by the player.
+#[derive(Debug, Default)]
+pub struct Stockpiles {
+    next_id: StockpileId,
+    piles: Vec<Stockpile>,
+    /// Number of each resource sitting in a stockpile, see `recount`.
+    stock: HashMap<String, u32>,
+}
+
+impl Stockpiles {
+    /// Designate the rectangle between <a> & <b> as a stockpile that stores
+    /// anything in <accepts>, or anything at all if it's empty.
+    pub fn designate(
+        &mut self,
+        a: Point3<u32>,
+        b: Point3<u32>,
+        accepts: HashSet<String>,
+    ) -> StockpileId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.piles.push(Stockpile {
+            id,
+            min: Point3::new(a.x.min(b.x), a.y.min(b.y), a.z),
+            max: Point3::new(a.x.max(b.x), a.y.max(b.y), a.z),
+            accepts,
+        });
+
+        id
+    }
+
+    pub fn remove(&mut self, id: StockpileId) {
+        self.piles.retain(|pile| pile.id != id);
+    }
+
+    pub fn get(&self, id: StockpileId) -> Option<&Stockpile> {
+        self.piles.iter().find(|pile| pile.id == id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Stockpile> {
+        self.piles.iter()
+    }
+
+    /// Stockpile covering <pt>, if any.
+    pub fn at(&self, pt: &Point3<u32>) -> Option<&Stockpile> {
+        self.piles.iter().find(|pile| pile.contains(pt))
+    }
+
+    /// Colony-wide count of <resource> sitting in stockpiles.
+    pub fn stock(&self, resource: &str) -> u32 {
+        self.stock.get(resource).copied().unwrap_or(0)
+    }
+
+    pub fn totals(&self) -> &HashMap<String, u32> {
+        &self.stock
+    }
+
+    /// Update the stock counts w/ the resource type of every stored item.
+    pub fn recount<'a, I>(&mut self, stored: I)
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        self.stock.clear();
+        for resource in stored {
+            *self.stock.entry(resource.to_string()).or_insert(0) += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Stockpiles;
+    use core::Point3;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_designate() {
+        let mut stockpiles = Stockpiles::default();
+        let wood: HashSet<String> = vec!["wood".to_string()].into_iter().collect();
+        let id = stockpiles.designate(Point3::new(3, 2, 0), Point3::new(1, 1, 0), wood);
+
+        let pile = stockpiles.get(id).unwrap();
+        assert_eq!(pile.tiles().count(), 6);
+        assert!(pile.accepts("wood"));
+        assert!(!pile.accepts("food"));
+        assert_eq!(stockpiles.at(&Point3::new(2, 2, 0)).unwrap().id, id);
+        assert!(stockpiles.at(&Point3::new(2, 2, 1)).is_none());
+
+        stockpiles.recount(vec!["wood", "wood"]);
+        assert_eq!(stockpiles.stock("wood"), 2);
+
+        stockpiles.remove(id);
+        assert!(stockpiles.at(&Point3::new(2, 2, 0)).is_none());
+    }
+}
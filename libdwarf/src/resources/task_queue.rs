@@ -1,20 +1,20 @@
-use crate::trigger::TriggerType;
+use crate::trigger::{EntityId, TriggerRecord, TriggerType};
 use std::collections::VecDeque;
 
 #[derive(Default)]
 pub struct TaskQueue {
     // World updates
-    pub world: VecDeque<TriggerType>,
-    // Worker tasks
-    pub worker: VecDeque<TriggerType>,
+    pub world: VecDeque<TriggerRecord>,
 }
 
 impl TaskQueue {
-    pub fn add(&mut self, action: TriggerType) {
-        self.worker.push_back(action.clone());
+    pub fn add_world(&mut self, action: TriggerType) {
+        self.world.push_back(TriggerRecord::new(action, None));
     }
 
-    pub fn add_world(&mut self, action: TriggerType) {
-        self.world.push_back(action.clone());
+    /// Queue a world update caused by <source>.
+    pub fn add_world_from(&mut self, source: EntityId, action: TriggerType) {
+        self.world
+            .push_back(TriggerRecord::new(action, Some(source)));
     }
 }
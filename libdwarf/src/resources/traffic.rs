@@ -0,0 +1,88 @@
+use core::Point3;
+use std::collections::HashMap;
+
+type WorkerId = u32;
+
+/// Number of upcoming tiles a worker reserves along its path. Other workers
+/// will wait rather than step into a reserved tile, which keeps workers from
+/// walking into each other in narrow corridors.
+pub const RESERVATION_WINDOW: usize = 3;
+
+/// Keeps track of which tiles workers are about to walk into & how long
+/// they've been stuck waiting on someone else.
+#[derive(Debug, Default)]
+pub struct Traffic {
+    /// Tile -> worker who has reserved it.
+    reserved: HashMap<Point3<u32>, WorkerId>,
+    /// Tiles each worker has reserved, next step first.
+    reservations: HashMap<WorkerId, Vec<Point3<u32>>>,
+    /// Number of ticks each worker has been blocked for.
+    waiting: HashMap<WorkerId, u32>,
+}
+
+impl Traffic {
+    /// Worker who has reserved <pt>, if any.
+    pub fn reserved_by(&self, pt: &Point3<u32>) -> Option<WorkerId> {
+        self.reserved.get(pt).copied()
+    }
+
+    /// Tiles <worker> has reserved, next step first.
+    pub fn reservations(&self, worker: WorkerId) -> &[Point3<u32>] {
+        self.reservations
+            .get(&worker)
+            .map(|tiles| tiles.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Reserve the next few steps of <path> for <worker>, replacing any
+    /// previous reservations. Stops at the first tile someone else has
+    /// already reserved.
+    ///
+    /// This is a plain look-ahead, not time-windowed like WHCA*: tiles are
+    /// held regardless of when the worker will actually reach them, so two
+    /// workers meeting head-on are left to `Map::step_worker` to sort out.
+    pub fn reserve(&mut self, worker: WorkerId, path: &[Point3<u32>]) {
+        self.release(worker);
+
+        let mut tiles = Vec::new();
+        for pt in path.iter().rev().take(RESERVATION_WINDOW) {
+            if self.reserved.contains_key(pt) {
+                break;
+            }
+
+            self.reserved.insert(*pt, worker);
+            tiles.push(*pt);
+        }
+
+        self.reservations.insert(worker, tiles);
+    }
+
+    /// Drop every reservation <worker> is holding.
+    pub fn release(&mut self, worker: WorkerId) {
+        if let Some(tiles) = self.reservations.remove(&worker) {
+            for pt in tiles {
+                if self.reserved.get(&pt) == Some(&worker) {
+                    self.reserved.remove(&pt);
+                }
+            }
+        }
+    }
+
+    /// Count another tick of <worker> being blocked. Returns the number of
+    /// ticks they've been waiting.
+    pub fn wait(&mut self, worker: WorkerId) -> u32 {
+        let ticks = self.waiting.entry(worker).or_insert(0);
+        *ticks += 1;
+        *ticks
+    }
+
+    pub fn stop_waiting(&mut self, worker: WorkerId) {
+        self.waiting.remove(&worker);
+    }
+
+    /// Forget everything about <worker>, e.g. once they've stopped moving.
+    pub fn clear(&mut self, worker: WorkerId) {
+        self.release(worker);
+        self.stop_waiting(worker);
+    }
+}
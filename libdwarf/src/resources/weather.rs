@@ -0,0 +1,189 @@
+use libterrain::Biome;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{
+    config::WeatherConfig,
+    resources::{Map, Season, SimRng},
+};
+
+/// What the sky's doing.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+pub enum WeatherKind {
+    Clear,
+    Rain,
+    Snow,
+    /// Too rough out to get any work done outside.
+    Storm,
+    /// Workers out in the sun get thirsty a lot quicker.
+    HeatWave,
+}
+
+impl fmt::Display for WeatherKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            WeatherKind::Clear => "clear",
+            WeatherKind::Rain => "rain",
+            WeatherKind::Snow => "snow",
+            WeatherKind::Storm => "storm",
+            WeatherKind::HeatWave => "heat wave",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Decides which kinds of weather show up, see `WeatherConfig::chances`.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+pub enum Climate {
+    Temperate,
+    Cold,
+}
+
+impl Climate {
+    pub fn of(biome: &Biome) -> Self {
+        match biome {
+            Biome::TAIGA | Biome::TUNDRA | Biome::SNOW => Climate::Cold,
+            _ => Climate::Temperate,
+        }
+    }
+
+    /// Climate of the biome most of the map's surface is covered in.
+    pub fn of_map(map: &Map) -> Self {
+        let mut counts: HashMap<Climate, u32> = HashMap::new();
+        for y in 0..map.height {
+            for x in 0..map.width {
+                let ground = map
+                    .surface_at(x, y)
+                    .and_then(|pt| map.ground_at(&pt))
+                    .filter(|biome| *biome != Biome::OCEAN);
+                if let Some(biome) = ground {
+                    *counts.entry(Climate::of(&biome)).or_insert(0) += 1;
+                }
+            }
+        }
+
+        counts
+            .into_iter()
+            .max_by_key(|(climate, count)| (*count, *climate == Climate::Temperate))
+            .map(|(climate, _)| climate)
+            .unwrap_or(Climate::Temperate)
+    }
+}
+
+/// Current weather over the map. Changes every so often based on the
+/// climate & season, see `WeatherSystem`.
+#[derive(Clone, Debug)]
+pub struct Weather {
+    pub kind: WeatherKind,
+    pub climate: Climate,
+    /// Seconds left until the weather changes.
+    pub remaining: f32,
+}
+
+impl Weather {
+    /// Starts off w/ a spell of clear weather.
+    pub fn new(climate: Climate, config: &WeatherConfig) -> Self {
+        Weather {
+            kind: WeatherKind::Clear,
+            climate,
+            remaining: config.min_duration,
+        }
+    }
+
+    /// Storms in cold climates are blizzards.
+    pub fn is_snowing(&self) -> bool {
+        match self.kind {
+            WeatherKind::Snow => true,
+            WeatherKind::Storm => self.climate == Climate::Cold,
+            _ => false,
+        }
+    }
+
+    pub fn is_raining(&self) -> bool {
+        match self.kind {
+            WeatherKind::Rain => true,
+            WeatherKind::Storm => self.climate != Climate::Cold,
+            _ => false,
+        }
+    }
+
+    /// Is it cold enough to hurt workers caught outside?
+    pub fn is_cold(&self) -> bool {
+        self.is_snowing()
+    }
+
+    /// Is it too rough out to get any work done outside?
+    pub fn stops_outdoor_work(&self) -> bool {
+        self.kind == WeatherKind::Storm
+    }
+
+    /// Roll for the next spell of weather. Returns true if it changed.
+    pub fn change(&mut self, season: Season, config: &WeatherConfig, rng: &mut SimRng) -> bool {
+        let chances = config
+            .chances
+            .get(&self.climate)
+            .and_then(|seasons| seasons.get(&season))
+            .map(|chances| chances.as_slice())
+            .unwrap_or(&[]);
+
+        let total: u32 = chances.iter().map(|(_, weight)| weight).sum();
+        let mut roll = rng.range(1, total.max(1));
+        let mut kind = WeatherKind::Clear;
+        for (option, weight) in chances {
+            if roll <= *weight {
+                kind = *option;
+                break;
+            }
+            roll -= weight;
+        }
+
+        let min = config.min_duration.min(config.max_duration);
+        let duration = rng.range(min as u32, config.max_duration as u32) as f32;
+        self.remaining = duration.max(1.0);
+
+        let changed = self.kind != kind;
+        self.kind = kind;
+        changed
+    }
+}
+
+impl fmt::Display for Weather {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Climate, Weather, WeatherKind};
+    use crate::{
+        config::WeatherConfig,
+        resources::{Season, SimRng},
+    };
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_weather() {
+        let mut seasons = HashMap::new();
+        seasons.insert(Season::Winter, vec![(WeatherKind::Storm, 1)]);
+        let mut config = WeatherConfig::default();
+        config.chances.insert(Climate::Cold, seasons);
+
+        let mut rng = SimRng::new(7);
+        let mut weather = Weather::new(Climate::Cold, &config);
+        assert!(!weather.change(Season::Summer, &config, &mut rng));
+        assert_eq!(weather.kind, WeatherKind::Clear);
+        assert!(weather.remaining >= config.min_duration);
+
+        // Storms in the cold are blizzards.
+        assert!(weather.change(Season::Winter, &config, &mut rng));
+        assert!(weather.stops_outdoor_work());
+        assert!(weather.is_snowing());
+        assert!(!weather.is_raining());
+
+        weather.climate = Climate::Temperate;
+        assert!(weather.is_raining());
+        assert!(!weather.is_cold());
+    }
+}
@@ -0,0 +1,154 @@
+use core::{
+    amethyst::ecs::{ReadExpect, System, WriteExpect},
+    log,
+};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::{
+    buildings::BlueprintLibrary,
+    config::{read_ron, ConfigPaths, ResourceConfig, WorldConfig},
+    creatures::CreatureLibrary,
+    crops::CropLibrary,
+    jobs::JobLibrary,
+    planner::Planner,
+    recipes::RecipeBook,
+};
+
+/// How often we check the data files for changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Watches the sim data files and swaps in the new version whenever one of
+/// them changes on disk. If the new version fails to parse, the old one is
+/// kept around and the error is logged.
+///
+/// NOTE: Objects copy their `ResourceType` when they are created, so changes
+/// to `resources.ron` only affect objects added after the reload.
+#[derive(Default)]
+pub struct ConfigReloadSystem {
+    last_poll: Option<Instant>,
+    /// Last seen modification time for each watched file.
+    modified: HashMap<PathBuf, SystemTime>,
+}
+
+impl ConfigReloadSystem {
+    /// Returns true if the file at <path> has been modified since the last
+    /// time we looked at it. The first check only records the current
+    /// modification time.
+    fn has_changed(&mut self, path: &Path) -> bool {
+        let modified = match fs::metadata(path).and_then(|meta| meta.modified()) {
+            Ok(modified) => modified,
+            Err(_) => return false,
+        };
+
+        match self.modified.insert(path.to_path_buf(), modified) {
+            Some(last_modified) => last_modified != modified,
+            None => false,
+        }
+    }
+}
+
+/// Reload <current> from <path>, keeping the current version on errors.
+fn reload<T: serde::de::DeserializeOwned>(current: &mut T, path: &Path) {
+    match read_ron(path) {
+        Ok(updated) => {
+            log::info!("Reloaded {}", path.display());
+            *current = updated;
+        }
+        Err(e) => log::error!("Failed to reload, keeping previous version: {}", e),
+    }
+}
+
+impl<'a> System<'a> for ConfigReloadSystem {
+    type SystemData = (
+        Option<ReadExpect<'a, ConfigPaths>>,
+        WriteExpect<'a, Planner>,
+        WriteExpect<'a, BlueprintLibrary>,
+        WriteExpect<'a, CreatureLibrary>,
+        WriteExpect<'a, CropLibrary>,
+        WriteExpect<'a, JobLibrary>,
+        WriteExpect<'a, RecipeBook>,
+        WriteExpect<'a, ResourceConfig>,
+        WriteExpect<'a, WorldConfig>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            paths,
+            mut planner,
+            mut blueprints,
+            mut creatures,
+            mut crops,
+            mut jobs,
+            mut recipes,
+            mut resources,
+            mut config,
+        ): Self::SystemData,
+    ) {
+        // Nothing to watch if the sim wasn't loaded from files.
+        let paths = match paths {
+            Some(paths) => paths,
+            None => return,
+        };
+
+        if let Some(last_poll) = self.last_poll {
+            if last_poll.elapsed() < POLL_INTERVAL {
+                return;
+            }
+        }
+        self.last_poll = Some(Instant::now());
+
+        if let Some(path) = &paths.actions {
+            if self.has_changed(path) {
+                reload(&mut *planner, path);
+            }
+        }
+
+        if let Some(path) = &paths.buildings {
+            if self.has_changed(path) {
+                reload(&mut *blueprints, path);
+            }
+        }
+
+        if let Some(path) = &paths.creatures {
+            if self.has_changed(path) {
+                reload(&mut *creatures, path);
+            }
+        }
+
+        if let Some(path) = &paths.crops {
+            if self.has_changed(path) {
+                reload(&mut *crops, path);
+            }
+        }
+
+        if let Some(path) = &paths.jobs {
+            if self.has_changed(path) {
+                reload(&mut *jobs, path);
+            }
+        }
+
+        if let Some(path) = &paths.recipes {
+            if self.has_changed(path) {
+                reload(&mut *recipes, path);
+            }
+        }
+
+        if let Some(path) = &paths.resources {
+            if self.has_changed(path) {
+                reload(&mut *resources, path);
+            }
+        }
+
+        if let Some(path) = &paths.sim_config {
+            if self.has_changed(path) {
+                reload(&mut *config, path);
+            }
+        }
+    }
+}
@@ -0,0 +1,365 @@
+use core::{
+    amethyst::ecs::{
+        Entities, Join, ReadExpect, ReadStorage, System, Write, WriteExpect, WriteStorage,
+    },
+    utils::is_near,
+    Point3,
+};
+use libterrain::TerrainChunk;
+use std::collections::HashMap;
+
+use crate::{
+    components::{Creature, EntityInfo, Worker},
+    config::WorldConfig,
+    creatures::{CreatureLibrary, CreatureType},
+    resources::{time::Time, Calendar, Map, SimRng, TaskQueue},
+    trigger::TriggerType,
+};
+
+/// Random spots to try when looking for somewhere a creature can spawn.
+const SPAWN_TRIES: u32 = 10;
+/// Odds each second of an idle creature wandering off somewhere.
+const WANDER_CHANCE: f32 = 0.2;
+/// Farthest an idle creature wanders off to, in tiles.
+const WANDER_RANGE: u32 = 5;
+/// Random spots a spooked animal considers when running off.
+const FLEE_TRIES: u32 = 4;
+/// Creatures give up the chase once the worker is this many times further
+/// away than the creature's aggression range.
+const CHASE_RANGE: usize = 2;
+
+/// Spawns creatures & moves them around. Hostile creatures hunt down any
+/// worker that comes within range & attack once they've caught up, wildlife
+/// runs off instead.
+pub struct CreatureSystem;
+impl<'a> System<'a> for CreatureSystem {
+    type SystemData = (
+        Entities<'a>,
+        WriteStorage<'a, Creature>,
+        WriteStorage<'a, EntityInfo>,
+        ReadStorage<'a, Worker>,
+        WriteExpect<'a, Map>,
+        ReadExpect<'a, CreatureLibrary>,
+        ReadExpect<'a, Calendar>,
+        ReadExpect<'a, Time>,
+        WriteExpect<'a, SimRng>,
+        Write<'a, TaskQueue>,
+        ReadExpect<'a, WorldConfig>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            mut creatures,
+            mut entity_infos,
+            workers,
+            mut map,
+            library,
+            calendar,
+            time,
+            mut rng,
+            mut tasks,
+            config,
+        ): Self::SystemData,
+    ) {
+        let delta = time.delta_seconds();
+        let prey: Vec<(u32, Point3<u32>)> = (&*entities, &workers, &entity_infos)
+            .join()
+            .map(|(entity, _, entity_info)| (entity.id(), entity_info.pos))
+            .collect();
+
+        let mut counts: HashMap<String, u32> = HashMap::new();
+        for (entity, creature, entity_info) in
+            (&*entities, &mut creatures, &mut entity_infos).join()
+        {
+            creature.health.update(delta, &config.health);
+            if creature.is_dead() {
+                tasks.add_world(TriggerType::Die(entity.id()));
+                continue;
+            }
+
+            *counts
+                .entry(creature.creature_type.name.clone())
+                .or_insert(0) += 1;
+
+            let pos = &mut entity_info.pos;
+            let target = if creature.creature_type.is_skittish() {
+                run_off(creature, pos, &prey, &map, &mut rng);
+                None
+            } else {
+                let target = hunt(creature, pos, &prey);
+                creature.target = target.map(|(id, _)| id);
+                target
+            };
+            let ready = creature.ready_to_attack(delta);
+
+            match target {
+                // Caught up, attack whenever it's ready to.
+                Some((worker, worker_pos)) if is_near(pos, &worker_pos) => {
+                    creature.path.clear();
+                    if ready {
+                        tasks.add_world_from(
+                            entity.id(),
+                            TriggerType::Attack {
+                                target: worker,
+                                source: entity.id(),
+                                damage: creature.creature_type.damage,
+                                accuracy: creature.creature_type.accuracy,
+                            },
+                        );
+                        creature.attacked();
+                    }
+                    continue;
+                }
+                // Worker moved, head to where they are now.
+                Some((_, worker_pos)) if creature.path.first() != Some(&worker_pos) => {
+                    creature.path = map.find_path(pos, &worker_pos).unwrap_or_default();
+                }
+                None if creature.target.is_none()
+                    && creature.path.is_empty()
+                    && rng.chance(WANDER_CHANCE * delta) =>
+                {
+                    creature.path = random_spot(pos, &map, &mut rng)
+                        .and_then(|destination| map.find_path(pos, &destination))
+                        .unwrap_or_default();
+                }
+                _ => {}
+            }
+
+            for _ in 0..creature.steps(delta) {
+                let caught_up = target
+                    .map(|(_, worker_pos)| is_near(pos, &worker_pos))
+                    .unwrap_or(false);
+                if caught_up {
+                    break;
+                }
+
+                match creature.path.pop() {
+                    Some(next) if !map.is_blocked(&next) => {
+                        map.move_creature(entity.id(), *pos, next);
+                        *pos = next;
+                    }
+                    // Something got in the way, figure it out next tick.
+                    Some(_) => {
+                        creature.path.clear();
+                        break;
+                    }
+                    None => break,
+                }
+            }
+        }
+
+        for id in library.creature_ids() {
+            let creature_type = match library.get(id) {
+                Ok(creature_type) => creature_type,
+                Err(_) => continue,
+            };
+
+            let count = counts.get(&creature_type.name).copied().unwrap_or(0);
+            if !creature_type.is_active(&calendar)
+                || count >= creature_type.spawn.max
+                || !rng.chance(creature_type.spawn.chance * delta)
+            {
+                continue;
+            }
+
+            if let Some(pt) = spawn_point(creature_type, &map, &mut rng) {
+                tasks.add_world(TriggerType::AddCreature(pt, id.clone()));
+            }
+        }
+    }
+}
+
+/// Worker the creature is going after, if any. Hostile creatures stick w/
+/// their current target until it gets away, otherwise they go after the
+/// closest worker in range.
+fn hunt(
+    creature: &Creature,
+    pos: &Point3<u32>,
+    prey: &[(u32, Point3<u32>)],
+) -> Option<(u32, Point3<u32>)> {
+    if !creature.creature_type.is_hostile() {
+        return None;
+    }
+
+    let range = creature.creature_type.aggression as usize;
+    let distance = |pt: &Point3<u32>| TerrainChunk::heuristic(pos, pt);
+    creature
+        .target
+        .and_then(|target| prey.iter().find(|(id, _)| *id == target))
+        .filter(|(_, pt)| distance(pt) <= range * CHASE_RANGE)
+        .or_else(|| {
+            prey.iter()
+                .filter(|(_, pt)| distance(pt) <= range)
+                .min_by_key(|(_, pt)| distance(pt))
+        })
+        .copied()
+}
+
+/// Runs from the closest worker within the creature's flee range, heading
+/// for whichever nearby spot is farthest from them. Picks a new spot when
+/// another worker spooks it or it gets where it was going.
+fn run_off(
+    creature: &mut Creature,
+    pos: &Point3<u32>,
+    prey: &[(u32, Point3<u32>)],
+    map: &Map,
+    rng: &mut SimRng,
+) {
+    let range = creature.creature_type.flee_range as usize;
+    let distance = |pt: &Point3<u32>| TerrainChunk::heuristic(pos, pt);
+    let threat = prey
+        .iter()
+        .filter(|(_, pt)| distance(pt) <= range)
+        .min_by_key(|(_, pt)| distance(pt));
+
+    let threat_id = threat.map(|(id, _)| *id);
+    let spooked = threat_id != creature.target;
+    creature.target = threat_id;
+
+    if let Some((_, worker_pos)) = threat {
+        if spooked || creature.path.is_empty() {
+            creature.path = (0..FLEE_TRIES)
+                .filter_map(|_| random_spot(pos, map, rng))
+                .max_by_key(|pt| TerrainChunk::heuristic(worker_pos, pt))
+                .and_then(|destination| map.find_path(pos, &destination))
+                .unwrap_or_default();
+        }
+    }
+}
+
+/// Random free spot near <pos>.
+fn random_spot(pos: &Point3<u32>, map: &Map, rng: &mut SimRng) -> Option<Point3<u32>> {
+    let x = rng.range(
+        pos.x.saturating_sub(WANDER_RANGE),
+        (pos.x + WANDER_RANGE).min(map.width.saturating_sub(1)),
+    );
+    let y = rng.range(
+        pos.y.saturating_sub(WANDER_RANGE),
+        (pos.y + WANDER_RANGE).min(map.height.saturating_sub(1)),
+    );
+
+    map.surface_at(x, y).filter(|pt| !map.is_blocked(pt))
+}
+
+/// Random free spot on the surface w/ a biome <creature_type> likes.
+fn spawn_point(creature_type: &CreatureType, map: &Map, rng: &mut SimRng) -> Option<Point3<u32>> {
+    (0..SPAWN_TRIES)
+        .filter_map(|_| {
+            let x = rng.range(0, map.width.saturating_sub(1));
+            let y = rng.range(0, map.height.saturating_sub(1));
+            map.surface_at(x, y)
+        })
+        .find(|pt| {
+            !map.is_blocked(pt)
+                && map
+                    .ground_at(pt)
+                    .map(|biome| creature_type.spawns_on(&biome))
+                    .unwrap_or(false)
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{hunt, CreatureSystem};
+    use crate::{
+        components::{Creature, EntityInfo, Worker},
+        creatures::CreatureLibrary,
+        resources::{time::Time, Map},
+        world::WorldSim,
+    };
+    use core::{
+        amethyst::ecs::{Builder, RunNow, World, WorldExt},
+        Point3,
+    };
+    use libterrain::TerrainChunk;
+    use std::path::PathBuf;
+
+    fn creature(name: &str) -> Creature {
+        let path =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../resources/data/creatures.ron");
+        let library = CreatureLibrary::load(path).unwrap();
+        Creature::new(library.get(name).unwrap())
+    }
+
+    #[test]
+    fn test_hunt() {
+        let pos = Point3::new(0, 0, 0);
+        let mut goblin = creature("goblin");
+        let range = goblin.creature_type.aggression;
+
+        // Goes after the closest worker in range.
+        let mut prey = vec![
+            (1, Point3::new(range, 0, 0)),
+            (2, Point3::new(3, 0, 0)),
+            (3, Point3::new(range + 1, 0, 0)),
+        ];
+        assert_eq!(hunt(&goblin, &pos, &prey), Some(prey[1]));
+
+        // Sticks w/ its target as it gets away, until it's out of chase range.
+        goblin.target = Some(3);
+        prey[2].1 = Point3::new(range * 2, 0, 0);
+        assert_eq!(hunt(&goblin, &pos, &prey), Some(prey[2]));
+        prey[2].1 = Point3::new(range * 2 + 1, 0, 0);
+        assert_eq!(hunt(&goblin, &pos, &prey), Some(prey[1]));
+
+        // Nobody close enough.
+        goblin.target = None;
+        assert_eq!(hunt(&goblin, &pos, &prey[2..]), None);
+
+        // Wildlife leaves workers alone.
+        assert_eq!(hunt(&creature("deer"), &pos, &prey), None);
+    }
+
+    #[test]
+    fn test_run_off() {
+        let mut world = World::new();
+        WorldSim::builder()
+            .with_data_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/../resources"))
+            .with_seed(42)
+            .build(&mut world, &TerrainChunk::new(12, 12), 12, 12)
+            .unwrap();
+
+        let worker_pos = Point3::new(5, 5, 0);
+        let start = Point3::new(6, 5, 0);
+        let worker = world
+            .create_entity()
+            .with(Worker::new(0))
+            .with(EntityInfo {
+                pos: worker_pos,
+                z_offset: 1.0,
+            })
+            .build();
+        let deer = world
+            .create_entity()
+            .with(creature("deer"))
+            .with(EntityInfo {
+                pos: start,
+                z_offset: 1.0,
+            })
+            .build();
+        world
+            .write_resource::<Map>()
+            .track_creature(deer.id(), start);
+        world.write_resource::<Time>().set_delta_seconds(1.0);
+
+        for _ in 0..3 {
+            CreatureSystem.run_now(&world);
+            world.maintain();
+        }
+
+        // Keeps an eye on the worker that spooked it while getting away.
+        let pos = world.read_storage::<EntityInfo>().get(deer).unwrap().pos;
+        let creatures = world.read_storage::<Creature>();
+        assert_eq!(creatures.get(deer).unwrap().target, Some(worker.id()));
+        assert!(
+            TerrainChunk::heuristic(&pos, &worker_pos)
+                > TerrainChunk::heuristic(&start, &worker_pos)
+        );
+        assert_eq!(
+            world.read_resource::<Map>().creature_at(&pos),
+            Some(deer.id())
+        );
+    }
+}
@@ -0,0 +1,32 @@
+use core::amethyst::ecs::{Entities, Join, ReadExpect, System, WriteStorage};
+
+use crate::{
+    components::{Crop, MapObject},
+    resources::Calendar,
+};
+
+/// Grows planted crops a tick at a time while they're in season.
+pub struct CropSystem;
+impl<'a> System<'a> for CropSystem {
+    type SystemData = (
+        Entities<'a>,
+        WriteStorage<'a, Crop>,
+        WriteStorage<'a, MapObject>,
+        ReadExpect<'a, Calendar>,
+    );
+
+    fn run(&mut self, (entities, mut crops, mut objects, calendar): Self::SystemData) {
+        for (entity, crop) in (&*entities, &mut crops).join() {
+            if !crop.crop_type.grows_in(calendar.season()) {
+                continue;
+            }
+
+            // Show off the next growth stage.
+            if crop.grow() {
+                if let Some(object) = objects.get_mut(entity) {
+                    object.resource_type.sprite = crop.sprite();
+                }
+            }
+        }
+    }
+}
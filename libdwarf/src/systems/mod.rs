@@ -1,9 +1,17 @@
+mod config_reload;
+mod creature_sim;
+mod crop_sim;
 mod object_sim;
 mod time_tick;
+mod weather_sim;
 mod worker_sim;
 mod world_sim;
 
+pub use config_reload::*;
+pub use creature_sim::*;
+pub use crop_sim::*;
 pub use object_sim::*;
 pub use time_tick::*;
+pub use weather_sim::*;
 pub use worker_sim::*;
 pub use world_sim::*;
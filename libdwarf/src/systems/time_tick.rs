@@ -1,15 +1,23 @@
-use core::amethyst::ecs::{System, WriteExpect};
+use core::amethyst::ecs::{ReadExpect, System, WriteExpect};
 
-use crate::resources::time;
+use crate::{
+    config::WorldConfig,
+    resources::{time, Calendar},
+};
 
 pub struct TimeTickSystem;
 impl<'a> System<'a> for TimeTickSystem {
     type SystemData = (
         WriteExpect<'a, time::Time>,
         WriteExpect<'a, time::Stopwatch>,
+        WriteExpect<'a, Calendar>,
+        ReadExpect<'a, WorldConfig>,
     );
 
-    fn run(&mut self, (mut time, mut stopwatch): Self::SystemData) {
+    fn run(&mut self, (mut time, mut stopwatch, mut calendar, config): Self::SystemData) {
+        // Keep the calendar in step w/ the time the other systems just used.
+        calendar.advance(time.delta_seconds(), &config.calendar);
+
         let elapsed = stopwatch.elapsed();
         time.increment_frame_number();
         time.set_delta_time(elapsed);
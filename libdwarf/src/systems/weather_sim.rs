@@ -0,0 +1,65 @@
+use core::amethyst::{
+    ecs::{ReadExpect, System, Write, WriteExpect},
+    shrev::EventChannel,
+};
+use core::log;
+
+use crate::{
+    config::WorldConfig,
+    events::SimEvent,
+    resources::{time::Time, Calendar, Map, SimRng, Weather},
+};
+
+/// Changes the weather every so often & applies its effects to the map,
+/// e.g. piling up snow or leaving puddles behind.
+pub struct WeatherSystem;
+impl<'a> System<'a> for WeatherSystem {
+    type SystemData = (
+        WriteExpect<'a, Weather>,
+        WriteExpect<'a, Map>,
+        WriteExpect<'a, SimRng>,
+        Write<'a, EventChannel<SimEvent>>,
+        ReadExpect<'a, Calendar>,
+        ReadExpect<'a, Time>,
+        ReadExpect<'a, WorldConfig>,
+    );
+
+    fn run(
+        &mut self,
+        (mut weather, mut map, mut rng, mut events, calendar, time, config): Self::SystemData,
+    ) {
+        let config = &config.weather;
+        let delta = time.delta_seconds();
+
+        weather.remaining -= delta;
+        if weather.remaining <= 0.0 {
+            let was_raining = weather.is_raining();
+            if weather.change(calendar.season(), config, &mut rng) {
+                log::info!("Weather changed to {}", weather.kind);
+                events.single_write(SimEvent::WeatherChanged {
+                    weather: weather.kind,
+                });
+            }
+
+            // Rain leaves puddles behind, which dry up once it's over.
+            if weather.is_raining() && !was_raining {
+                map.puddles = (0..config.puddles)
+                    .filter_map(|_| {
+                        let x = rng.range(0, map.width.saturating_sub(1));
+                        let y = rng.range(0, map.height.saturating_sub(1));
+                        map.surface_at(x, y)
+                    })
+                    .collect();
+            } else if !weather.is_raining() {
+                map.puddles.clear();
+            }
+        }
+
+        // Snow piles up while it's snowing & slowly melts away otherwise.
+        map.snow_cover = if weather.is_snowing() {
+            (map.snow_cover + config.snow_fall * delta).min(1.0)
+        } else {
+            (map.snow_cover - config.snow_melt * delta).max(0.0)
+        };
+    }
+}
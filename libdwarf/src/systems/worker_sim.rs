@@ -1,15 +1,31 @@
-use core::amethyst::ecs::{
-    Entities, Join, ReadExpect, ReadStorage, System, Write, WriteExpect, WriteStorage,
+use core::amethyst::{
+    ecs::{
+        Entities, Join, Read, ReadExpect, ReadStorage, System, Write, WriteExpect, WriteStorage,
+    },
+    shrev::EventChannel,
 };
+use libterrain::TerrainChunk;
+use std::{cmp::Reverse, collections::HashMap};
 
 use crate::{
-    components::{EntityInfo, MapObject, Worker, WorkerAction},
+    components::{
+        ActionTarget, Construction, Creature, Crop, EntityInfo, Inventory, MapObject, Need,
+        ToolClass, Worker, WorkerAction,
+    },
     config::WorldConfig,
-    planner::{Condition, Planner, State},
-    resources::{time::Time, Map, TaskQueue},
-    trigger::TriggerType,
+    crops::CropLibrary,
+    events::SimEvent,
+    goals::{self, FarmWork, Goal, GoalContext, StorageSpot, Threat},
+    jobs::JobLibrary,
+    planner::{Planner, State},
+    recipes::RecipeBook,
+    resources::{
+        time::Time, Calendar, JobBoard, JobState, Map, ProductionQueue, TaskQueue, Weather,
+        WeatherKind, ITEMS_PER_TILE,
+    },
+    trigger::{TriggerPriority, TriggerType},
 };
-use core::utils::is_near;
+use core::{log, Point3};
 
 pub struct WorkerSystem;
 impl<'a> System<'a> for WorkerSystem {
@@ -18,10 +34,22 @@ impl<'a> System<'a> for WorkerSystem {
         WriteStorage<'a, Worker>,
         ReadStorage<'a, MapObject>,
         WriteStorage<'a, EntityInfo>,
+        ReadStorage<'a, Inventory>,
+        ReadStorage<'a, Construction>,
+        ReadStorage<'a, Crop>,
+        ReadStorage<'a, Creature>,
         WriteExpect<'a, Map>,
         WriteExpect<'a, Planner>,
+        ReadExpect<'a, JobLibrary>,
+        ReadExpect<'a, RecipeBook>,
+        ReadExpect<'a, CropLibrary>,
+        Read<'a, ProductionQueue>,
         Write<'a, TaskQueue>,
+        Write<'a, JobBoard>,
+        Write<'a, EventChannel<SimEvent>>,
         ReadExpect<'a, Time>,
+        ReadExpect<'a, Calendar>,
+        ReadExpect<'a, Weather>,
         ReadExpect<'a, WorldConfig>,
     );
 
@@ -32,79 +60,734 @@ impl<'a> System<'a> for WorkerSystem {
             mut workers,
             objects,
             mut entity_infos,
+            inventories,
+            constructions,
+            crops,
+            creatures,
             mut map,
             planner,
+            jobs,
+            recipes,
+            crop_types,
+            production,
             mut tasks,
+            mut board,
+            mut events,
             time,
+            calendar,
+            weather,
             config,
         ): Self::SystemData,
     ) {
-        for (_entity, worker, entity_info) in (&*entities, &mut workers, &mut entity_infos).join() {
+        // Snapshot of the world used to decide what idle workers should do.
+        let mut ctx = GoalContext {
+            stock_targets: config.stock_targets.clone(),
+            claimed: board.targets().collect(),
+            water: map.water_sources().copied().collect(),
+            is_night: calendar.is_night(),
+            is_storm: weather.stops_outdoor_work(),
+            ..Default::default()
+        };
+        for (entity, object, entity_info) in (&*entities, &objects, &entity_infos).join() {
+            ctx.objects.push((entity.id(), entity_info.pos, object));
+            *ctx.stock
+                .entry(object.resource_type.name.clone())
+                .or_insert(0) += 1;
+        }
+
+        for (entity, site, entity_info) in (&*entities, &constructions, &entity_infos).join() {
+            ctx.sites.push((entity.id(), entity_info.pos, site));
+            for (resource, count) in site.missing() {
+                *ctx.demand.entry(resource.clone()).or_insert(0) += count;
+            }
+        }
+
+        // Where each creature is, workers need to keep track of the ones
+        // they're fighting or hunting as they move around.
+        let mut creature_pos = HashMap::new();
+        for (entity, creature, entity_info) in (&*entities, &creatures, &entity_infos).join() {
+            creature_pos.insert(entity.id(), entity_info.pos);
+            if creature.creature_type.is_hostile() && !creature.is_dead() {
+                ctx.threats.push(Threat {
+                    id: entity.id(),
+                    position: entity_info.pos,
+                    health: creature.health.hp.ceil() as i32,
+                    range: creature.creature_type.aggression,
+                });
+            } else if !creature.is_dead() {
+                ctx.animals.push((entity.id(), entity_info.pos));
+            }
+        }
+
+        // Find out what's been stored so far & where there's room for more.
+        let mut stored = Vec::new();
+        for pile in map.stockpiles.iter() {
+            for pt in pile.tiles() {
+                ctx.stockpiled.insert(pt);
+                if map.is_solid(&pt) {
+                    continue;
+                }
+
+                let items: Vec<&str> = map
+                    .stack_at(&pt)
+                    .map(|stack| stack.items.as_slice())
+                    .unwrap_or(&[])
+                    .iter()
+                    .filter_map(|id| objects.get(entities.entity(*id)))
+                    .map(|object| object.resource_type.name.as_str())
+                    .collect();
+
+                if items.len() < ITEMS_PER_TILE {
+                    ctx.storage.push(StorageSpot {
+                        position: pt,
+                        accepts: pile.accepts.clone(),
+                        resource: items.first().map(|name| name.to_string()),
+                    });
+                }
+                stored.extend(items);
+            }
+        }
+        map.stockpiles.recount(stored);
+
+        // Farm tiles that need tilling, planting or harvesting.
+        for plot in map.farms.iter() {
+            let crop_type = match crop_types.get(&plot.crop) {
+                Ok(crop_type) => crop_type,
+                Err(_) => continue,
+            };
+
+            for pt in plot.tiles.iter() {
+                if map.is_solid(pt) || !map.terrain.is_walkable(pt) {
+                    continue;
+                }
+
+                let planted = map.stack_at(pt).and_then(|stack| {
+                    stack.items.iter().find_map(|id| {
+                        crops
+                            .get(entities.entity(*id))
+                            .map(|crop| (*id, crop.is_grown()))
+                    })
+                });
+
+                let work = match planted {
+                    Some((id, true)) => FarmWork::Harvest(id),
+                    Some((_, false)) => continue,
+                    None if !plot.is_tilled(pt) => FarmWork::Till,
+                    None if crop_type.grows_in(calendar.season()) => FarmWork::Plant,
+                    None => continue,
+                };
+                ctx.farm_work.push((*pt, plot.crop.clone(), work));
+            }
+        }
+
+        // Workers that are free to take on a new job.
+        let mut idle = Vec::new();
+        for (entity, worker, entity_info) in (&*entities, &workers, &entity_infos).join() {
+            ctx.workers.push((entity.id(), entity_info.pos));
+            if worker.job.is_none()
+                && worker.order.is_none()
+                && worker.current_action.is_none()
+                && worker.queue.is_empty()
+                && board.claimed_by(entity.id()).is_none()
+            {
+                idle.push((entity.id(), entity_info.pos));
+            }
+
+            if let Some(inventory) = inventories.get(entity) {
+                for stack in inventory.stacks.iter() {
+                    *ctx.stock.entry(stack.resource.clone()).or_insert(0) += stack.count;
+                    // Anything being carried around can go to a site.
+                    if let Some(demand) = ctx.demand.get_mut(&stack.resource) {
+                        *demand = demand.saturating_sub(stack.count);
+                    }
+                }
+            }
+
+            // Don't send multiple workers after the same thing.
+            if let Some(goal) = &worker.goal {
+                if let Some(target) = goal.target() {
+                    ctx.claimed.insert(target);
+                }
+                ctx.on_the_way(goal);
+            }
+        }
+
+        // Production orders that still need work.
+        let outputs = |id: &str| {
+            recipes
+                .get(id)
+                .map(|recipe| recipe.outputs.keys().cloned().collect())
+                .unwrap_or_default()
+        };
+        ctx.recipes = production
+            .pending(&ctx.stock, outputs)
+            .filter_map(|order| {
+                let recipe = recipes.get(&order.recipe).ok()?;
+                Some((order.recipe.clone(), recipe))
+            })
+            .collect();
+
+        // Hand out open jobs to the most skilled idle worker that's able to
+        // do them, going w/ the closest one when there's a tie.
+        let open_jobs: Vec<_> = board
+            .open_jobs()
+            .iter()
+            .map(|job| (job.id, job.labor, job.position()))
+            .collect();
+        for (job_id, labor, position) in open_jobs {
+            let best = idle
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, (id, pos))| match workers.get(entities.entity(*id)) {
+                    Some(worker) if worker.can_do(labor) => {
+                        Some((idx, pos, worker.skills.level_for(labor)))
+                    }
+                    _ => None,
+                })
+                .min_by_key(|(_, pos, level)| {
+                    let distance =
+                        position.map_or(0, |target| TerrainChunk::heuristic(pos, &target));
+                    (Reverse(*level), distance)
+                })
+                .map(|(idx, _, _)| idx);
+
+            if let Some(idx) = best {
+                let (worker_id, _) = idle.remove(idx);
+                board.claim(job_id, worker_id);
+            }
+        }
+
+        for (entity, worker, entity_info) in (&*entities, &mut workers, &mut entity_infos).join() {
+            // Bleed out or heal up.
+            worker.health.update(time.delta_seconds(), &config.health);
+            if worker.health.is_dead() {
+                tasks.add_world(TriggerType::Die(entity.id()));
+                continue;
+            }
+
             // Regen worker energy.
             if worker.energy < config.worker_stamina {
                 // NOTE: This might need to be revisited. Pausing the simulation would
                 // mean a large `time.delta_seconds()` which immediately resets
                 // the worker energy, leading to a burst of actions for a couple frames
                 // before going back to normal.
-                worker.energy = (worker.energy + (config.worker_stamina * time.delta_seconds()))
-                    .min(config.worker_stamina);
+                // Workers w/ unmet needs are slower to get going again.
+                let stamina = config.worker_stamina * worker.needs.work_rate(&config.needs);
+                worker.energy =
+                    (worker.energy + (stamina * time.delta_seconds())).min(config.worker_stamina);
             }
 
-            if worker.energy < config.action_cost {
+            worker.needs.decay(time.delta_seconds(), &config.needs);
+
+            // Workers caught outside suffer through the weather.
+            if !map.is_sheltered(&entity_info.pos) {
+                if weather.is_cold() {
+                    let cold = config.weather.cold_drain * time.delta_seconds();
+                    worker.needs.drain(Need::Rest, cold);
+                } else if weather.kind == WeatherKind::HeatWave {
+                    let heat = config.weather.heat_drain * time.delta_seconds();
+                    worker.needs.drain(Need::Thirst, heat);
+                }
+            }
+
+            // Skilled workers tire less quickly.
+            let action_cost = worker.action_cost(config.action_cost);
+            if worker.energy < action_cost {
                 continue;
             }
 
-            // Assign new task if the current action was completed.
-            if worker.current_action.is_none() {
-                let current_pos = &entity_info.pos;
+            // Sleep it off where they dropped.
+            if worker.collapsed {
+                worker
+                    .needs
+                    .restore(Need::Rest, config.needs.rough_sleep, &config.needs);
+                worker.energy -= config.action_cost;
+                if worker.needs.rest >= config.needs.wake_threshold {
+                    worker.collapsed = false;
+                    events.single_write(SimEvent::WorkerRecovered {
+                        worker: entity.id(),
+                    });
+                }
+                continue;
+            }
+
+            if worker.needs.rest <= 0.0 {
+                log::info!("Worker {} collapsed from exhaustion", entity.id());
+                worker.collapsed = true;
+                worker.order = None;
+                worker.abandon_plan();
+                map.traffic.clear(entity.id());
+                events.single_write(SimEvent::WorkerCollapsed {
+                    worker: entity.id(),
+                });
+                continue;
+            }
+
+            // Drop low priority work to take care of urgent needs or get out
+            // of a storm, once the current action is done.
+            let is_busy = worker.current_action.is_none()
+                && !worker.queue.is_empty()
+                && !matches!(worker.goal, Some(Goal::Satisfy { .. }));
+            if is_busy {
+                let is_important = board
+                    .claimed_by(entity.id())
+                    .map(|job| job.priority >= TriggerPriority::HIGH)
+                    .unwrap_or(false);
+                let urgent = Need::PHYSICAL.iter().find(|need| {
+                    worker.needs.is_urgent(**need, &config.needs)
+                        && Goal::satisfy(
+                            **need,
+                            entity.id(),
+                            inventories.get(entity),
+                            &entity_info.pos,
+                            &ctx,
+                        )
+                        .is_some()
+                });
+
+                if let (false, Some(need)) = (is_important, urgent) {
+                    log::info!("Worker {} dropping work, needs {}", entity.id(), need);
+                    worker.abandon_plan();
+                } else if !is_important && ctx.is_storm {
+                    log::info!(
+                        "Worker {} dropping work, waiting out the storm",
+                        entity.id()
+                    );
+                    worker.abandon_plan();
+                }
+            }
+
+            // Deal w/ any hostile creature that's come too close, whatever
+            // the worker was up to.
+            let is_fighting = matches!(
+                worker.goal,
+                Some(Goal::Fight { .. }) | Some(Goal::Flee { .. })
+            );
+            let threatened = ctx
+                .threats
+                .iter()
+                .any(|threat| threat.in_range(&entity_info.pos));
+            if threatened && !is_fighting && worker.goal.is_some() && worker.order.is_none() {
+                log::info!("Worker {} dropping work, under attack", entity.id());
+                worker.abandon_plan();
+            }
+
+            // Player orders come before anything the worker wants to do.
+            if worker.order.is_some() {
+                worker.follow_order(&mut events, &mut map, entity_info);
+                worker.energy -= config.action_cost * map.move_cost(&entity_info.pos) as f32;
+                continue;
+            }
+
+            // Pick a new goal once the worker has run out of things to do.
+            if worker.current_action.is_none() && worker.queue.is_empty() {
+                // Made it through the plan for the job's current step, move on
+                // to the next one. Abandoned plans don't count.
+                if let (Some(Goal::Step { .. }), Some(job)) = (worker.goal.take(), &mut worker.job)
+                {
+                    job.step += 1;
+                }
+
+                let current_pos = entity_info.pos;
                 let mut state = State::new();
                 worker.add_state(&mut state);
+                if let Some(inventory) = inventories.get(entity) {
+                    inventory.add_state(&mut state);
+                }
 
-                if let Some(TriggerType::HarvestResource {
-                    target,
-                    position,
-                    resource,
-                }) = tasks.worker.pop_front()
-                {
-                    // Are we already near the resource?
-                    state.insert(
-                        Condition::Near(resource.clone()),
-                        is_near(&position, &current_pos),
-                    );
+                if let Some(mut job) = worker.job.take() {
+                    job.skip_done(&state);
+                    if job.is_done() {
+                        log::info!("Worker {} finished {}", entity.id(), job);
+                    } else {
+                        worker.job = Some(job);
+                    }
+                }
+
+                let mut posting = board.claimed_by(entity.id()).cloned();
+                if let Some(job) = posting.clone() {
+                    match job.state {
+                        // Nothing left to do for this job.
+                        JobState::InProgress(_) if worker.job.is_none() => {
+                            board.finish(job.id, true);
+                            events.single_write(SimEvent::JobCompleted {
+                                job: job.id,
+                                worker: entity.id(),
+                                task: job.task,
+                            });
+                            posting = None;
+                        }
+                        // Worker can't do this job anymore, let someone else have it.
+                        JobState::Claimed(_) if !worker.can_do(job.labor) => {
+                            board.release(job.id);
+                            posting = None;
+                        }
+                        _ => {}
+                    }
+                }
 
-                    // Create the desired state
-                    let mut desired_state = State::new();
-                    desired_state.insert(Condition::Has(resource.clone()), true);
+                // Job we'd start if the worker picks the claimed task.
+                let mut new_job = None;
+                // Next step of the current job or task claimed from the job
+                // board, if it's still valid.
+                let player_goal = match &worker.job {
+                    Some(job) => job
+                        .next_step()
+                        .and_then(|step| Goal::from_step(step, &current_pos, &ctx)),
+                    None => match posting.as_ref().map(|job| &job.task) {
+                        Some(TriggerType::HarvestResource {
+                            target,
+                            position,
+                            resource,
+                        }) => ctx.object(*target).map(|(_, _, object)| Goal::Gather {
+                            target: *target,
+                            position: *position,
+                            source: object.resource_type.name.clone(),
+                            resource: resource.clone(),
+                        }),
+                        Some(TriggerType::Hunt { target }) => ctx
+                            .animals
+                            .iter()
+                            .find(|(id, _)| id == target)
+                            .map(|(id, position)| Goal::Hunt {
+                                target: *id,
+                                position: *position,
+                            }),
+                        Some(TriggerType::StartJob(job)) => {
+                            new_job = jobs.decompose(job, &state);
+                            new_job
+                                .as_ref()
+                                .and_then(|job| job.next_step())
+                                .and_then(|step| Goal::from_step(step, &current_pos, &ctx))
+                        }
+                        _ => None,
+                    },
+                };
 
-                    // Plan stuff
-                    let mut planned = planner.plan(&state, &desired_state);
-                    while let Some(action) = planned.pop() {
-                        // Convert planned actions into actions for the worker.
-                        let entity = entities.entity(target);
+                if player_goal.is_none() {
+                    if let Some(job) = worker.job.take() {
+                        log::warn!("Worker {} unable to continue {}", entity.id(), job);
+                    }
+
+                    // Target is gone or there's no way to do this job.
+                    if let Some(job) = posting.take() {
+                        log::warn!("Worker {} unable to do {:?}", entity.id(), job.task);
+                        board.finish(job.id, false);
+                    }
+                }
+
+                let mut scored = goals::score_goals(
+                    entity.id(),
+                    &worker.needs,
+                    inventories.get(entity),
+                    &current_pos,
+                    &ctx,
+                    &config.needs,
+                    player_goal.clone(),
+                );
+                let combat =
+                    Goal::combat(&current_pos, worker.attack_damage(), &worker.health, &ctx);
+                if let Some(goal) = combat {
+                    scored.insert(0, (goals::COMBAT_SCORE, goal));
+                }
+
+                // Go with the highest scoring goal we can come up with a plan for.
+                for (_score, goal) in scored {
+                    let is_player_goal = Some(&goal) == player_goal.as_ref();
+                    if !is_player_goal && !worker.can_do(goal.labor()) {
+                        continue;
+                    }
+
+                    let mut initial_state = state.clone();
+                    goal.add_state(&mut initial_state, &current_pos);
+
+                    let planned = planner.plan(&initial_state, &goal.desired_state());
+                    if planned.is_empty() {
+                        continue;
+                    }
+
+                    // Actions that go after a tool, e.g. grabbing an axe
+                    // before chopping down a tree, head for the closest one
+                    // instead of the goal's target.
+                    let tools: Option<HashMap<ToolClass, (u32, Point3<u32>)>> = planned
+                        .iter()
+                        .filter_map(|action| action.subject())
+                        .filter(|name| Some(*name) != goal.subject())
+                        .filter_map(ToolClass::from_name)
+                        .map(|class| {
+                            ctx.nearest_tool(&current_pos, class)
+                                .map(|(id, pos, _)| (class, (*id, *pos)))
+                        })
+                        .collect();
+                    let tools = match tools {
+                        Some(tools) => tools,
+                        // No tool to get the work done w/.
+                        None => continue,
+                    };
+
+                    if is_player_goal {
+                        if let Some(job) = &posting {
+                            board.start(job.id);
+                        }
+
+                        if worker.job.is_none() {
+                            worker.job = new_job.take();
+                        }
+                    }
+
+                    if let Some(target) = goal.target() {
+                        ctx.claimed.insert(target);
+                    }
+                    ctx.claimed.extend(tools.values().map(|(id, _)| *id));
+                    ctx.on_the_way(&goal);
+
+                    // Convert planned actions into actions for the worker.
+                    let goal_target = (goal.target(), goal.target_pos().unwrap_or(current_pos));
+                    for action in planned.into_iter().rev() {
+                        let (target, target_pos) = action
+                            .subject()
+                            .and_then(ToolClass::from_name)
+                            .and_then(|class| tools.get(&class))
+                            .map(|(id, pos)| (Some(*id), *pos))
+                            .unwrap_or(goal_target);
                         worker.queue.push_back(WorkerAction {
-                            target: Some(entity.id()),
-                            action: action.clone(),
-                            target_pos: position,
+                            target,
+                            action,
+                            target_pos,
+                            progress: 0,
                         });
                     }
+
+                    log::debug!("Worker {} picked goal {:?}", entity.id(), goal);
+                    worker.goal = Some(goal);
+                    break;
                 }
+            }
 
+            if worker.current_action.is_none() {
                 worker.current_action = worker.queue.pop_front();
             }
 
             // Process current worker action
             if worker.current_action.is_some() {
                 // Grab the latest target info, if any.
-                let mut target_obj = None;
-                if let Some(target_id) = worker.current_action.as_ref().unwrap().target {
+                let mut target = ActionTarget::default();
+                let action = worker.current_action.as_ref().unwrap();
+                if let Some(stack) = map.stack_at(&action.target_pos) {
+                    target.items = stack
+                        .items
+                        .iter()
+                        .filter_map(|id| objects.get(entities.entity(*id)).map(|obj| (*id, obj)))
+                        .collect();
+                }
+                if let Some(target_id) = action.target {
                     let entity = entities.entity(target_id);
-                    target_obj = objects.get(entity);
+                    target.object = objects.get(entity);
+                    target.site = constructions.get(entity);
+                    target.creature = creatures.get(entity);
+                    target.position = creature_pos.get(&target_id).copied();
                 }
 
-                worker.do_work(&mut tasks, &mut map, entity_info, target_obj);
+                worker.do_work(
+                    &mut tasks,
+                    &mut events,
+                    &mut map,
+                    entity_info,
+                    target,
+                    &config.needs,
+                );
             }
 
-            worker.energy -= config.action_cost;
+            // Trudging through snow takes more out of a worker.
+            if worker.current_path.is_some() {
+                worker.energy -= action_cost * map.move_cost(&entity_info.pos) as f32;
+            } else {
+                worker.energy -= action_cost;
+            }
+
+            // Let others through once this worker has stopped moving.
+            if worker.current_path.is_none() && worker.order.is_none() {
+                map.traffic.clear(entity.id());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::WorkerSystem;
+    use crate::{
+        components::{Construction, Equipment, Inventory, ItemStack, ToolClass, Worker},
+        events::SimEvent,
+        resources::{time::Time, JobBoard, JobId, Map, TaskQueue},
+        systems::{ObjectSystem, WorldUpdateSystem},
+        trigger::{TriggerPriority, TriggerType},
+        world::WorldSim,
+    };
+    use core::{
+        amethyst::{
+            ecs::{Join, RunNow, World, WorldExt},
+            shrev::{EventChannel, ReaderId},
+        },
+        Point3,
+    };
+    use libterrain::TerrainChunk;
+
+    /// World w/ an empty <width> x <height> map & a worker at <pos>.
+    fn setup(width: u32, height: u32, pos: Point3<u32>) -> (World, u32) {
+        let terrain = TerrainChunk::new(width, height);
+        let mut world = World::new();
+        WorldSim::builder()
+            .with_data_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/../resources"))
+            .with_seed(42)
+            .build(&mut world, &terrain, width, height)
+            .unwrap();
+        // Give workers the energy to do something each tick.
+        world.write_resource::<Time>().set_delta_seconds(0.5);
+
+        world
+            .write_resource::<TaskQueue>()
+            .add_world(TriggerType::AddWorker(pos));
+        tick(&mut world);
+
+        let worker = world.read_resource::<Map>().workers_at(&pos)[0];
+        (world, worker)
+    }
+
+    /// Let workers act & apply the changes they made to the world.
+    fn tick(world: &mut World) {
+        WorkerSystem.run_now(world);
+        ObjectSystem.run_now(world);
+        WorldUpdateSystem.run_now(world);
+        world.maintain();
+    }
+
+    /// Hand <job> to <worker> from the job board.
+    fn assign(world: &mut World, worker: u32, job: &str) -> JobId {
+        let mut board = world.write_resource::<JobBoard>();
+        let id = board
+            .post(
+                TriggerType::StartJob(job.to_string()),
+                TriggerPriority::MEDIUM,
+                None,
+            )
+            .unwrap();
+        board.claim(id, worker);
+        id
+    }
+
+    /// Tick until <job> is done, returns false if it takes too long.
+    fn run_job(world: &mut World, reader: &mut ReaderId<SimEvent>, job: JobId) -> bool {
+        for _ in 0..100 {
+            tick(world);
+            let channel = world.read_resource::<EventChannel<SimEvent>>();
+            let completed = channel
+                .read(reader)
+                .any(|event| matches!(event, SimEvent::JobCompleted { job: id, .. } if *id == job));
+            if completed {
+                return true;
+            }
         }
+
+        false
+    }
+
+    #[test]
+    fn test_build_job() {
+        let (mut world, worker) = setup(6, 3, Point3::new(0, 1, 0));
+        let mut reader = world
+            .write_resource::<EventChannel<SimEvent>>()
+            .register_reader();
+
+        let site_pos = Point3::new(4, 1, 0);
+        world
+            .write_resource::<TaskQueue>()
+            .add_world(TriggerType::PlaceBlueprint {
+                blueprint: "wall".to_string(),
+                position: site_pos,
+            });
+        tick(&mut world);
+
+        // Someone else already brought part of what the wall needs & the
+        // worker is carrying the rest, so there's no tree to chop.
+        for site in (&mut world.write_storage::<Construction>()).join() {
+            site.deliver("wood", 1);
+        }
+        world
+            .write_storage::<Inventory>()
+            .get_mut(world.entities().entity(worker))
+            .unwrap()
+            .add(ItemStack::new("wood", 1, 2));
+
+        let job = assign(&mut world, worker, "build");
+        assert!(run_job(&mut world, &mut reader, job));
+        assert_eq!((&world.read_storage::<Construction>()).join().count(), 0);
+        assert!(!world.read_resource::<Map>().terrain.is_walkable(&site_pos));
+        let workers = world.read_storage::<Worker>();
+        let worker = workers.get(world.entities().entity(worker)).unwrap();
+        assert!(worker.job.is_none());
+    }
+
+    #[test]
+    fn test_fetch_tool() {
+        let (mut world, worker) = setup(6, 3, Point3::new(0, 1, 0));
+        let mut reader = world
+            .write_resource::<EventChannel<SimEvent>>()
+            .register_reader();
+        let (axe_pos, tree_pos) = (Point3::new(2, 0, 0), Point3::new(4, 1, 0));
+        {
+            let mut tasks = world.write_resource::<TaskQueue>();
+            tasks.add_world(TriggerType::Add(axe_pos, "axe".to_string()));
+            tasks.add_world(TriggerType::Add(tree_pos, "tree".to_string()));
+        }
+        tick(&mut world);
+
+        // Lost the axe they started out with.
+        {
+            let mut workers = world.write_storage::<Worker>();
+            let worker = workers.get_mut(world.entities().entity(worker)).unwrap();
+            worker.equipment = Equipment::default();
+        }
+
+        let job = assign(&mut world, worker, "gather_wood");
+        tick(&mut world);
+
+        // Going for the axe is part of the plan for chopping the tree.
+        let axe = world
+            .read_resource::<Map>()
+            .stack_at(&axe_pos)
+            .unwrap()
+            .top()
+            .unwrap();
+        {
+            let workers = world.read_storage::<Worker>();
+            let worker = workers.get(world.entities().entity(worker)).unwrap();
+            let planned: Vec<(&str, Option<u32>)> = worker
+                .current_action
+                .iter()
+                .chain(worker.queue.iter())
+                .map(|action| (action.action.name.as_str(), action.target))
+                .collect();
+            let tree = planned[2].1;
+            assert_eq!(
+                planned,
+                vec![
+                    ("walk_to_axe", Some(axe)),
+                    ("equip_axe", Some(axe)),
+                    ("find_wood", tree),
+                    ("harvest_wood", tree),
+                    ("pickup_wood", tree),
+                ]
+            );
+            assert_ne!(tree, Some(axe));
+        }
+
+        assert!(run_job(&mut world, &mut reader, job));
+        let workers = world.read_storage::<Worker>();
+        let worker = workers.get(world.entities().entity(worker)).unwrap();
+        assert!(worker.equipment.has(ToolClass::Axe));
+        let inventories = world.read_storage::<Inventory>();
+        let inventory = inventories.get(world.entities().entity(worker.id)).unwrap();
+        assert_eq!(inventory.count("wood"), 1);
     }
 }
@@ -1,15 +1,35 @@
 use core::{
-    amethyst::ecs::{Entities, ReadExpect, System, WriteExpect, WriteStorage},
-    log,
+    amethyst::{
+        ecs::{Entities, Join, ReadExpect, System, Write, WriteExpect, WriteStorage},
+        shrev::EventChannel,
+    },
+    log, Point3,
+};
+use libterrain::Biome;
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
 };
 
 use crate::{
-    components::{EntityInfo, MapObject, Worker},
-    config::ResourceConfig,
-    resources::{Map, TaskQueue},
-    trigger::TriggerType,
+    buildings::{BlueprintLibrary, Construct},
+    components::{
+        Construction, Creature, Crop, EntityInfo, Health, Inventory, ItemStack, MapObject,
+        ResourceType, Skills, Slot, Worker,
+    },
+    config::{ResourceConfig, WorldConfig},
+    creatures::CreatureLibrary,
+    crops::CropLibrary,
+    events::SimEvent,
+    recipes::RecipeBook,
+    resources::{JobBoard, Map, ProductionQueue, SimRng, TaskQueue},
+    trigger::{TriggerRecord, TriggerType},
 };
 
+/// Resource left behind when a worker or creature dies, unless the creature
+/// leaves something else, e.g. an animal carcass.
+const CORPSE: &str = "corpse";
+
 #[derive(Default)]
 pub struct WorldUpdateSystem;
 impl<'a> System<'a> for WorldUpdateSystem {
@@ -18,45 +38,149 @@ impl<'a> System<'a> for WorldUpdateSystem {
         WriteStorage<'a, Worker>,
         WriteStorage<'a, MapObject>,
         WriteStorage<'a, EntityInfo>,
+        WriteStorage<'a, Inventory>,
+        WriteStorage<'a, Construction>,
+        WriteStorage<'a, Crop>,
+        WriteStorage<'a, Creature>,
         WriteExpect<'a, TaskQueue>,
         WriteExpect<'a, Map>,
         ReadExpect<'a, ResourceConfig>,
+        ReadExpect<'a, BlueprintLibrary>,
+        ReadExpect<'a, RecipeBook>,
+        ReadExpect<'a, CropLibrary>,
+        ReadExpect<'a, CreatureLibrary>,
+        Write<'a, ProductionQueue>,
+        Write<'a, JobBoard>,
+        ReadExpect<'a, WorldConfig>,
+        Write<'a, EventChannel<SimEvent>>,
+        WriteExpect<'a, SimRng>,
     );
 
     fn run(
         &mut self,
-        (entities, mut workers, mut objects, mut entity_infos, mut tasks, mut map, resources): Self::SystemData,
+        (
+            entities,
+            mut workers,
+            mut objects,
+            mut entity_infos,
+            mut inventories,
+            mut constructions,
+            mut crops,
+            mut creatures,
+            mut tasks,
+            mut map,
+            resources,
+            blueprints,
+            recipes,
+            crop_types,
+            creature_types,
+            mut production,
+            mut board,
+            config,
+            mut events,
+            mut rng,
+        ): Self::SystemData,
     ) {
-        let queue = &mut tasks.world;
-        while let Some(event) = queue.pop_front() {
-            match event {
+        // Highest priority first. Sorting is stable, so triggers w/ the same
+        // priority are handled in the order they were queued up.
+        let mut records: Vec<TriggerRecord> = tasks.world.drain(..).collect();
+        records.sort_by_key(|record| Reverse(record.priority));
+
+        // Entities that have been destroyed or picked up this tick. Anything
+        // else that acts on them after that fails.
+        let mut removed = HashSet::new();
+        for record in records {
+            let is_removed = record
+                .trigger
+                .target()
+                .map(|target| removed.contains(&target))
+                .unwrap_or(false);
+
+            if is_removed {
+                log::debug!("Target already gone, skipping {:?}", record.trigger);
+                if let Some(source) = record.source {
+                    if let Some(worker) = workers.get_mut(entities.entity(source)) {
+                        worker.trigger_failed(&record.trigger);
+                    }
+                }
+                continue;
+            }
+
+            match record.trigger {
                 // Add an object to the map.
                 TriggerType::Add(pt, name) => {
                     log::info!("Adding object '{}' @ ({:?})", name, pt);
-                    let resource = resources.map.get(&name).unwrap().clone();
-                    let new_entity = entities.create();
-                    objects
-                        .insert(new_entity, MapObject::new(&resource))
-                        .unwrap();
-                    entity_infos
-                        .insert(
-                            new_entity,
-                            EntityInfo {
-                                pos: pt,
-                                z_offset: 1.0,
-                            },
-                        )
-                        .unwrap();
-                    map.track_object(new_entity.id(), pt);
+                    let resource = match resources.get(&name) {
+                        Ok(resource) => resource,
+                        Err(err) => {
+                            log::warn!("Unable to add '{}': {}", name, err);
+                            continue;
+                        }
+                    };
+                    add_object(
+                        &entities,
+                        &mut objects,
+                        &mut entity_infos,
+                        &mut map,
+                        resource,
+                        pt,
+                    );
                 }
                 TriggerType::AddWorker(pos) => {
                     log::info!("Adding worker @ ({:?})", pos);
                     let entity = entities.create();
-                    workers.insert(entity, Worker::new(entity.id())).unwrap();
+                    let mut worker = Worker::new(entity.id());
+                    worker.skills = Skills::random(&mut rng);
+                    worker.health = Health::new(config.health.worker_health);
+                    for name in config.starting_tools.iter() {
+                        match resources.get(name).map(|resource| resource.tool()) {
+                            Ok(Some(tool)) => {
+                                worker.equipment.equip(tool);
+                            }
+                            _ => log::warn!("Starting tool '{}' isn't a tool", name),
+                        }
+                    }
+                    workers.insert(entity, worker).unwrap();
+                    inventories
+                        .insert(entity, Inventory::new(config.carry_weight))
+                        .unwrap();
                     entity_infos
                         .insert(entity, EntityInfo { pos, z_offset: 1.0 })
                         .unwrap();
                     map.track_worker(entity.id(), pos);
+                    events.single_write(SimEvent::WorkerSpawned {
+                        id: entity.id(),
+                        position: pos,
+                    });
+                }
+                TriggerType::AddCreature(pos, name) => {
+                    let creature_type = match creature_types.get(&name) {
+                        Ok(creature_type) => creature_type,
+                        Err(err) => {
+                            log::warn!("Unable to add creature: {}", err);
+                            continue;
+                        }
+                    };
+
+                    if map.is_blocked(&pos) {
+                        log::warn!("Unable to add {} @ ({:?}), tile is taken", name, pos);
+                        continue;
+                    }
+
+                    log::info!("Adding {} @ ({:?})", name, pos);
+                    let entity = entities.create();
+                    map.track_creature(entity.id(), pos);
+                    creatures
+                        .insert(entity, Creature::new(creature_type))
+                        .unwrap();
+                    entity_infos
+                        .insert(entity, EntityInfo { pos, z_offset: 1.0 })
+                        .unwrap();
+                    events.single_write(SimEvent::CreatureSpawned {
+                        id: entity.id(),
+                        creature: name,
+                        position: pos,
+                    });
                 }
                 // Deal damage to a particular object
                 TriggerType::DealDamage {
@@ -69,27 +193,667 @@ impl<'a> System<'a> for WorldUpdateSystem {
                         object.health -= damage;
                     }
                 }
+                // Swing at a worker or creature. Whoever's looking after it
+                // notices once it's dead.
+                TriggerType::Attack {
+                    source,
+                    target,
+                    damage,
+                    accuracy,
+                } => {
+                    let entity = entities.entity(target);
+                    let health = match (workers.get_mut(entity), creatures.get_mut(entity)) {
+                        (Some(worker), _) => &mut worker.health,
+                        (_, Some(creature)) => &mut creature.health,
+                        _ => continue,
+                    };
+                    if health.is_dead() {
+                        continue;
+                    }
+
+                    let hit = rng.chance(accuracy as f32 / 100.0);
+                    if hit {
+                        health.wound(damage, source, &config.health);
+                    }
+                    events.single_write(SimEvent::Attacked {
+                        attacker: source,
+                        target,
+                        damage: if hit { Some(damage) } else { None },
+                    });
+                }
+                TriggerType::Die(id) => {
+                    removed.insert(id);
+                    let entity = entities.entity(id);
+                    let position = match entity_infos.get(entity) {
+                        Some(entity_info) => entity_info.pos,
+                        None => continue,
+                    };
+
+                    // Everything they were carrying ends up on the ground.
+                    let mut items = inventories
+                        .get_mut(entity)
+                        .map(|inventory| inventory.stacks.drain(..).collect())
+                        .unwrap_or_else(Vec::new);
+
+                    let corpse = if let Some(worker) = workers.get(entity) {
+                        log::info!("Worker {} died @ ({:?})", id, position);
+                        for tool in Slot::ALL
+                            .iter()
+                            .filter_map(|slot| worker.equipment.get(*slot))
+                        {
+                            if let Ok(resource) = resources.get(&tool.resource) {
+                                items.push(ItemStack::new(&tool.resource, 1, resource.weight));
+                            }
+                        }
+
+                        map.remove_worker(id, position);
+                        map.traffic.clear(id);
+                        // Let someone else pick up where they left off.
+                        board.abandon(id);
+                        events.single_write(SimEvent::WorkerDied {
+                            worker: id,
+                            position,
+                            killer: worker.health.last_attacker,
+                        });
+                        CORPSE.to_string()
+                    } else if let Some(creature) = creatures.get(entity) {
+                        log::info!("{} died @ ({:?})", creature.creature_type.name, position);
+                        map.remove_creature(id, position);
+                        events.single_write(SimEvent::CreatureKilled {
+                            id,
+                            creature: creature.creature_type.name.clone(),
+                            position,
+                            killer: creature.health.last_attacker,
+                        });
+                        creature
+                            .creature_type
+                            .corpse
+                            .clone()
+                            .unwrap_or_else(|| CORPSE.to_string())
+                    } else {
+                        continue;
+                    };
+
+                    entities.delete(entity).unwrap();
+                    match resources.get(&corpse) {
+                        Ok(corpse) => {
+                            add_object(
+                                &entities,
+                                &mut objects,
+                                &mut entity_infos,
+                                &mut map,
+                                corpse,
+                                position,
+                            );
+                        }
+                        Err(err) => log::warn!("Unable to leave a corpse: {}", err),
+                    }
+                    scatter_items(
+                        &entities,
+                        &mut objects,
+                        &mut entity_infos,
+                        &mut map,
+                        &resources,
+                        items,
+                        position,
+                    );
+                }
                 // Destroy an object.
                 TriggerType::Destroy(id) => {
-                    // Remove from map
+                    removed.insert(id);
                     let entity = entities.entity(id);
-                    if objects.get(entity).is_some() {
+                    // Already gone, e.g. destroyed in an earlier tick.
+                    if objects.get(entity).is_none() {
+                        continue;
+                    }
+
+                    // Remove from map
+                    let mut drops = Vec::new();
+                    let mut position = None;
+                    if let Some(object) = objects.get(entity) {
                         if let Some(entity_info) = entity_infos.get(entity) {
                             map.remove_object(id, entity_info.pos);
+                            events.single_write(SimEvent::ObjectDestroyed {
+                                id,
+                                resource: object.resource_type.name.clone(),
+                                position: entity_info.pos,
+                            });
+                            drops = object.roll_drops(&mut rng);
+                            position = Some(entity_info.pos);
                         }
                     }
                     // Remove from world
                     entities.delete(entity).unwrap();
+
+                    // Scatter any drops around where the object used to be.
+                    if let Some(position) = position {
+                        for (name, count) in drops {
+                            let resource = match resources.get(&name) {
+                                Ok(resource) => resource,
+                                Err(err) => {
+                                    log::warn!("Unable to drop '{}': {}", name, err);
+                                    continue;
+                                }
+                            };
+
+                            for pt in map.scatter(&position, count as usize) {
+                                add_object(
+                                    &entities,
+                                    &mut objects,
+                                    &mut entity_infos,
+                                    &mut map,
+                                    resource,
+                                    pt,
+                                );
+                            }
+                        }
+                    }
+                }
+                TriggerType::Consume { owner, resource } => {
+                    if let Some(inventory) = inventories.get_mut(entities.entity(owner)) {
+                        inventory.remove(&resource, 1);
+                    }
+                }
+                TriggerType::Drop {
+                    owner,
+                    resource,
+                    count,
+                    position,
+                } => {
+                    let dropped = inventories
+                        .get_mut(entities.entity(owner))
+                        .and_then(|inventory| inventory.remove(&resource, count));
+                    let resource_type = match resources.get(&resource) {
+                        Ok(resource_type) => resource_type,
+                        Err(err) => {
+                            log::warn!("Unable to drop '{}': {}", resource, err);
+                            continue;
+                        }
+                    };
+
+                    if let Some(dropped) = dropped {
+                        // Pile everything up in one spot unless there's
+                        // something in the way.
+                        let spots = if map.is_solid(&position) {
+                            map.scatter(&position, dropped.count as usize)
+                        } else {
+                            vec![position; dropped.count as usize]
+                        };
+
+                        for pt in spots {
+                            let id = add_object(
+                                &entities,
+                                &mut objects,
+                                &mut entity_infos,
+                                &mut map,
+                                resource_type,
+                                pt,
+                            );
+
+                            // Hang on to what the items were made of.
+                            if let Some(object) =
+                                id.and_then(|id| objects.get_mut(entities.entity(id)))
+                            {
+                                object.material = dropped.material.clone();
+                                object.quality = dropped.quality;
+                            }
+                        }
+                    }
                 }
                 TriggerType::Take { target, owner } => {
                     let target_entity = entities.entity(target);
-                    if objects.get(target_entity).is_some() {
-                        if let Some(worker) = workers.get_mut(entities.entity(owner)) {
-                            worker.inventory.push(target);
+                    // Only items still lying around on the map can be taken.
+                    let object = objects
+                        .get(target_entity)
+                        .filter(|object| object.is_item())
+                        .filter(|_| entity_infos.get(target_entity).is_some());
+
+                    let owner_entity = entities.entity(owner);
+                    let picked_up = match (inventories.get_mut(owner_entity), object) {
+                        // Only take it if there's room to carry it.
+                        (Some(inventory), Some(object)) => {
+                            let item = ItemStack::from_object(object);
+                            if item.total_weight() + inventory.weight() <= inventory.max_weight {
+                                inventory.add(item);
+                                Some(object.resource_type.name.clone())
+                            } else {
+                                None
+                            }
+                        }
+                        _ => None,
+                    };
+
+                    match picked_up {
+                        Some(resource) => {
                             if let Some(entity_info) = entity_infos.get(target_entity) {
-                                map.remove_object(target_entity.id(), entity_info.pos);
-                                entity_infos.remove(target_entity);
+                                map.remove_object(target, entity_info.pos);
+                            }
+                            // Now tracked by the inventory.
+                            entities.delete(target_entity).unwrap();
+                            removed.insert(target);
+                            events.single_write(SimEvent::ItemPickedUp {
+                                item: target,
+                                owner,
+                                resource,
+                            });
+                        }
+                        None => {
+                            if let Some(worker) = workers.get_mut(owner_entity) {
+                                worker.trigger_failed(&record.trigger);
+                            }
+                        }
+                    }
+                }
+                TriggerType::Equip { worker, target } => {
+                    let target_entity = entities.entity(target);
+                    // Only tools still lying around on the map can be equipped.
+                    let tool = objects
+                        .get(target_entity)
+                        .filter(|object| object.is_item())
+                        .filter(|_| entity_infos.get(target_entity).is_some())
+                        .and_then(|object| object.resource_type.tool());
+
+                    let tool = match (workers.get_mut(entities.entity(worker)), tool) {
+                        (Some(worker), Some(tool)) => worker.equipment.equip(tool),
+                        (Some(worker), None) => {
+                            worker.trigger_failed(&record.trigger);
+                            continue;
+                        }
+                        _ => continue,
+                    };
+
+                    let position = entity_infos.get(target_entity).unwrap().pos;
+                    map.remove_object(target, position);
+                    entities.delete(target_entity).unwrap();
+                    removed.insert(target);
+
+                    // Drop whatever tool we had to make room for this one.
+                    if let Some(replaced) = tool {
+                        if let Ok(resource) = resources.get(&replaced.resource) {
+                            add_object(
+                                &entities,
+                                &mut objects,
+                                &mut entity_infos,
+                                &mut map,
+                                resource,
+                                position,
+                            );
+                        }
+                    }
+                }
+                TriggerType::PlaceBlueprint {
+                    blueprint,
+                    position,
+                } => {
+                    let blueprint = match blueprints.get(&blueprint) {
+                        Ok(blueprint) => blueprint,
+                        Err(err) => {
+                            log::warn!("Unable to place blueprint: {}", err);
+                            continue;
+                        }
+                    };
+
+                    // Sites can't overlap each other or anything solid.
+                    let occupied: HashSet<Point3<u32>> = (&constructions, &entity_infos)
+                        .join()
+                        .flat_map(|(site, entity_info)| site.blueprint.tiles(&entity_info.pos))
+                        .collect();
+                    let is_clear = blueprint.tiles(&position).iter().all(|pt| {
+                        pt.x < map.width
+                            && pt.y < map.height
+                            && map.terrain.is_walkable(pt)
+                            && !map.is_solid(pt)
+                            && !occupied.contains(pt)
+                    });
+
+                    if !is_clear {
+                        log::info!("No room for '{}' @ ({:?})", blueprint.name, position);
+                        continue;
+                    }
+
+                    let entity = entities.create();
+                    constructions
+                        .insert(entity, Construction::new(blueprint))
+                        .unwrap();
+                    entity_infos
+                        .insert(
+                            entity,
+                            EntityInfo {
+                                pos: position,
+                                z_offset: 1.0,
+                            },
+                        )
+                        .unwrap();
+                }
+                TriggerType::Deliver {
+                    owner,
+                    site,
+                    ref resource,
+                } => {
+                    let owner_entity = entities.entity(owner);
+                    match (
+                        inventories.get_mut(owner_entity),
+                        constructions.get_mut(entities.entity(site)),
+                    ) {
+                        (Some(inventory), Some(construction)) => {
+                            let needed = construction.needs(resource);
+                            if let Some(items) = inventory.remove(resource, needed) {
+                                construction.deliver(resource, items.count);
+                            }
+                        }
+                        _ => {
+                            if let Some(worker) = workers.get_mut(owner_entity) {
+                                worker.trigger_failed(&record.trigger);
+                            }
+                        }
+                    }
+                }
+                TriggerType::Build { worker, site, work } => {
+                    let site_entity = entities.entity(site);
+                    let is_done = match constructions.get_mut(site_entity) {
+                        Some(construction) => {
+                            construction.work(work);
+                            construction.is_done()
+                        }
+                        None => {
+                            if let Some(worker) = workers.get_mut(entities.entity(worker)) {
+                                worker.trigger_failed(&record.trigger);
+                            }
+                            continue;
+                        }
+                    };
+
+                    let position = match entity_infos.get(site_entity) {
+                        Some(entity_info) if is_done => entity_info.pos,
+                        _ => continue,
+                    };
+
+                    // Wait for anyone standing on the site to get out of the way,
+                    // workers & creatures alike.
+                    let blueprint = constructions.get(site_entity).unwrap().blueprint.clone();
+                    let tiles = blueprint.tiles(&position);
+                    let is_occupied = |pt: &Point3<u32>| {
+                        !map.workers_at(pt).is_empty() || map.creature_at(pt).is_some()
+                    };
+                    if tiles.iter().any(is_occupied) {
+                        continue;
+                    }
+
+                    match &blueprint.result {
+                        Construct::Block { height } => {
+                            // Push anything lying around onto the builder's tile.
+                            let builder_pos = entity_infos
+                                .get(entities.entity(worker))
+                                .map(|entity_info| entity_info.pos)
+                                .unwrap_or(position);
+                            for pt in tiles.iter() {
+                                let items = map
+                                    .stack_at(pt)
+                                    .map(|stack| stack.items.clone())
+                                    .unwrap_or_default();
+                                for item in items {
+                                    map.remove_object(item, *pt);
+                                    map.track_object(item, builder_pos, true);
+                                    if let Some(entity_info) =
+                                        entity_infos.get_mut(entities.entity(item))
+                                    {
+                                        entity_info.pos = builder_pos;
+                                    }
+                                }
+
+                                for z in pt.z..pt.z + height {
+                                    let block = Point3::new(pt.x as i32, pt.y as i32, z as i32);
+                                    if map.is_inside_map(block) {
+                                        map.terrain.set((pt.x, pt.y, z), Some(Biome::ROCK));
+                                    }
+                                }
+                            }
+                        }
+                        Construct::Object(name) => match resources.get(name) {
+                            Ok(resource) => {
+                                add_object(
+                                    &entities,
+                                    &mut objects,
+                                    &mut entity_infos,
+                                    &mut map,
+                                    resource,
+                                    position,
+                                );
+                            }
+                            Err(err) => {
+                                log::warn!("Unable to finish '{}': {}", blueprint.name, err)
+                            }
+                        },
+                    }
+
+                    log::info!("Finished building '{}' @ ({:?})", blueprint.name, position);
+                    entities.delete(site_entity).unwrap();
+                    removed.insert(site);
+                    events.single_write(SimEvent::ConstructionFinished {
+                        site,
+                        blueprint: blueprint.name.clone(),
+                        position,
+                    });
+                }
+                TriggerType::Craft {
+                    worker,
+                    workshop,
+                    ref recipe,
+                } => {
+                    let worker_entity = entities.entity(worker);
+                    let is_workshop = |name: &str| match objects.get(entities.entity(workshop)) {
+                        Some(object) => object.is_workshop() && object.resource_type.name == name,
+                        None => false,
+                    };
+                    let has_inputs = |inventory: &Inventory, inputs: &HashMap<String, u32>| {
+                        inputs
+                            .iter()
+                            .all(|(resource, count)| inventory.count(resource) >= *count)
+                    };
+
+                    let crafted = match (recipes.get(recipe), inventories.get_mut(worker_entity)) {
+                        (Ok(crafting), Some(inventory))
+                            if is_workshop(&crafting.workshop)
+                                && has_inputs(inventory, &crafting.inputs) =>
+                        {
+                            // Use up the inputs, which might be spread over a
+                            // couple stacks.
+                            for (resource, count) in crafting.inputs.iter() {
+                                let mut left = *count;
+                                while left > 0 {
+                                    match inventory.remove(resource, left) {
+                                        Some(used) => left -= used.count,
+                                        None => break,
+                                    }
+                                }
                             }
+
+                            Some(give_items(inventory, &resources, &crafting.outputs))
+                        }
+                        _ => None,
+                    };
+
+                    let leftovers = match crafted {
+                        Some(leftovers) => leftovers,
+                        None => {
+                            if let Some(worker) = workers.get_mut(worker_entity) {
+                                worker.trigger_failed(&record.trigger);
+                            }
+                            continue;
+                        }
+                    };
+
+                    // Drop whatever the worker can't carry.
+                    let position = entity_infos.get(worker_entity).map(|info| info.pos);
+                    if let Some(position) = position {
+                        scatter_items(
+                            &entities,
+                            &mut objects,
+                            &mut entity_infos,
+                            &mut map,
+                            &resources,
+                            leftovers,
+                            position,
+                        );
+                    }
+
+                    production.made(recipe);
+                    events.single_write(SimEvent::ItemsCrafted {
+                        worker,
+                        recipe: recipe.clone(),
+                    });
+                }
+                TriggerType::Till { worker, position } => {
+                    let is_clear = map.terrain.is_walkable(&position) && !map.is_solid(&position);
+                    if !is_clear || !map.farms.till(&position) {
+                        if let Some(worker) = workers.get_mut(entities.entity(worker)) {
+                            worker.trigger_failed(&record.trigger);
+                        }
+                    }
+                }
+                TriggerType::Plant {
+                    worker,
+                    position,
+                    ref crop,
+                } => {
+                    let is_tilled = matches!(
+                        map.farms.at(&position),
+                        Some(plot) if plot.crop == *crop && plot.is_tilled(&position)
+                    );
+                    let is_planted = map
+                        .stack_at(&position)
+                        .map(|stack| {
+                            stack
+                                .items
+                                .iter()
+                                .any(|id| crops.get(entities.entity(*id)).is_some())
+                        })
+                        .unwrap_or(false);
+
+                    let crop_type = match crop_types.get(crop) {
+                        Ok(crop_type) if is_tilled && !is_planted => crop_type,
+                        _ => {
+                            if let Some(worker) = workers.get_mut(entities.entity(worker)) {
+                                worker.trigger_failed(&record.trigger);
+                            }
+                            continue;
+                        }
+                    };
+
+                    // Crops can be walked over, so they sit w/ the items on
+                    // the tile rather than blocking it.
+                    let entity = entities.create();
+                    map.track_object(entity.id(), position, true);
+                    objects
+                        .insert(entity, MapObject::new(&crop_type.resource_type()))
+                        .unwrap();
+                    crops.insert(entity, Crop::new(crop_type)).unwrap();
+                    entity_infos
+                        .insert(
+                            entity,
+                            EntityInfo {
+                                pos: position,
+                                z_offset: 1.0,
+                            },
+                        )
+                        .unwrap();
+                }
+                TriggerType::Harvest { worker, crop } => {
+                    let worker_entity = entities.entity(worker);
+                    let crop_entity = entities.entity(crop);
+                    let harvested = match (
+                        crops.get(crop_entity),
+                        entity_infos.get(crop_entity),
+                        inventories.get_mut(worker_entity),
+                    ) {
+                        (Some(planted), Some(entity_info), Some(inventory))
+                            if planted.is_grown() =>
+                        {
+                            let leftovers =
+                                give_items(inventory, &resources, &planted.crop_type.yields);
+                            Some((planted.crop_type.name.clone(), entity_info.pos, leftovers))
+                        }
+                        _ => None,
+                    };
+
+                    let (name, position, leftovers) = match harvested {
+                        Some(harvested) => harvested,
+                        None => {
+                            if let Some(worker) = workers.get_mut(worker_entity) {
+                                worker.trigger_failed(&record.trigger);
+                            }
+                            continue;
+                        }
+                    };
+
+                    map.remove_object(crop, position);
+                    entities.delete(crop_entity).unwrap();
+                    removed.insert(crop);
+
+                    // Drop whatever the worker can't carry.
+                    scatter_items(
+                        &entities,
+                        &mut objects,
+                        &mut entity_infos,
+                        &mut map,
+                        &resources,
+                        leftovers,
+                        position,
+                    );
+
+                    events.single_write(SimEvent::CropHarvested {
+                        worker,
+                        crop: name,
+                        position,
+                    });
+                }
+                // Player ordered a worker somewhere, figure out how to get there.
+                TriggerType::MoveTo { worker, position } => {
+                    let entity = entities.entity(worker);
+                    let from = match entity_infos.get(entity) {
+                        Some(entity_info) => entity_info.pos,
+                        None => continue,
+                    };
+
+                    let path = if map.is_blocked(&position) {
+                        None
+                    } else {
+                        map.find_path(&from, &position)
+                    };
+
+                    match (workers.get_mut(entity), path) {
+                        (Some(worker), Some(path)) => worker.give_order(TriggerType::Move {
+                            worker: worker.id,
+                            path,
+                        }),
+                        (Some(_), None) => {
+                            log::info!("Worker {} can't reach {:?}", worker, position);
+                            events.single_write(SimEvent::PathFailed {
+                                worker,
+                                from,
+                                to: position,
+                            });
+                        }
+                        (None, _) => {}
+                    }
+                }
+                // Player gave a worker a path to follow.
+                TriggerType::Move { worker, path } => {
+                    let entity = entities.entity(worker);
+                    let from = match entity_infos.get(entity) {
+                        Some(entity_info) => entity_info.pos,
+                        None => continue,
+                    };
+
+                    if let Some(worker_comp) = workers.get_mut(entity) {
+                        if map.is_valid_path(&from, &path) {
+                            worker_comp.give_order(TriggerType::Move { worker, path });
+                        } else {
+                            events.single_write(SimEvent::PathFailed {
+                                worker,
+                                from,
+                                to: path.first().copied().unwrap_or(from),
+                            });
                         }
                     }
                 }
@@ -98,3 +862,742 @@ impl<'a> System<'a> for WorldUpdateSystem {
         }
     }
 }
+
+/// Create a new object from <resource> & place it on the map. Returns the
+/// new entity's id, or `None` if there's no room for it at <pt>.
+fn add_object(
+    entities: &Entities,
+    objects: &mut WriteStorage<MapObject>,
+    entity_infos: &mut WriteStorage<EntityInfo>,
+    map: &mut Map,
+    resource: &ResourceType,
+    pt: Point3<u32>,
+) -> Option<u32> {
+    let object = MapObject::new(resource);
+    let entity = entities.create();
+    if !map.track_object(entity.id(), pt, object.is_item()) {
+        log::warn!("No room for '{}' @ ({:?})", resource.name, pt);
+        entities.delete(entity).unwrap();
+        return None;
+    }
+
+    objects.insert(entity, object).unwrap();
+    entity_infos
+        .insert(
+            entity,
+            EntityInfo {
+                pos: pt,
+                z_offset: 1.0,
+            },
+        )
+        .unwrap();
+
+    Some(entity.id())
+}
+
+/// Put <items> in <inventory>, returning whatever doesn't fit.
+fn give_items(
+    inventory: &mut Inventory,
+    resources: &ResourceConfig,
+    items: &HashMap<String, u32>,
+) -> Vec<ItemStack> {
+    let mut leftovers = Vec::new();
+    for (resource, count) in items.iter() {
+        let weight = match resources.get(resource) {
+            Ok(resource_type) => resource_type.weight,
+            Err(err) => {
+                log::warn!("Unable to make '{}': {}", resource, err);
+                continue;
+            }
+        };
+
+        if let Some(leftover) = inventory.add(ItemStack::new(resource, *count, weight)) {
+            leftovers.push(leftover);
+        }
+    }
+
+    leftovers
+}
+
+/// Spread <items> out on the map around <position>, e.g. ones a worker
+/// couldn't carry.
+fn scatter_items(
+    entities: &Entities,
+    objects: &mut WriteStorage<MapObject>,
+    entity_infos: &mut WriteStorage<EntityInfo>,
+    map: &mut Map,
+    resources: &ResourceConfig,
+    items: Vec<ItemStack>,
+    position: Point3<u32>,
+) {
+    for item in items {
+        let resource_type = match resources.get(&item.resource) {
+            Ok(resource_type) => resource_type,
+            Err(_) => continue,
+        };
+
+        for pt in map.scatter(&position, item.count as usize) {
+            add_object(entities, objects, entity_infos, map, resource_type, pt);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::WorldUpdateSystem;
+    use crate::{
+        components::{Construction, Creature, Crop, Inventory, ItemStack, MapObject, Worker},
+        crops::CropLibrary,
+        events::SimEvent,
+        goals::Goal,
+        resources::{JobBoard, JobState, Map, TaskQueue},
+        trigger::{TriggerPriority, TriggerType},
+        world::WorldSim,
+    };
+    use core::{
+        amethyst::{
+            ecs::{Join, RunNow, World, WorldExt},
+            shrev::{EventChannel, ReaderId},
+        },
+        Point3,
+    };
+    use libterrain::{Biome, TerrainChunk};
+
+    /// World w/ an empty <width> x <height> map & a reader for the events
+    /// sent out while updating it.
+    fn setup(width: u32, height: u32) -> (World, ReaderId<SimEvent>) {
+        setup_with(&TerrainChunk::new(width, height))
+    }
+
+    fn setup_with(terrain: &TerrainChunk) -> (World, ReaderId<SimEvent>) {
+        let mut world = World::new();
+        WorldSim::builder()
+            .with_data_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/../resources"))
+            .with_seed(42)
+            .build(&mut world, terrain, terrain.width, terrain.height)
+            .unwrap();
+        let reader = world
+            .write_resource::<EventChannel<SimEvent>>()
+            .register_reader();
+        (world, reader)
+    }
+
+    /// Process everything queued up for the world & apply the changes.
+    fn tick(world: &mut World) {
+        WorldUpdateSystem.run_now(world);
+        world.maintain();
+    }
+
+    fn events(world: &World, reader: &mut ReaderId<SimEvent>) -> Vec<SimEvent> {
+        world
+            .read_resource::<EventChannel<SimEvent>>()
+            .read(reader)
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn test_worker_spawned() {
+        let (mut world, mut reader) = setup(4, 4);
+        let position = Point3::new(1, 1, 0);
+        world
+            .write_resource::<TaskQueue>()
+            .add_world(TriggerType::AddWorker(position));
+        tick(&mut world);
+
+        let id = world.read_resource::<Map>().workers_at(&position)[0];
+        assert_eq!(
+            events(&world, &mut reader),
+            vec![SimEvent::WorkerSpawned { id, position }]
+        );
+    }
+
+    #[test]
+    fn test_add_unknown() {
+        let (mut world, _) = setup(4, 4);
+        {
+            let mut tasks = world.write_resource::<TaskQueue>();
+            tasks.add_world(TriggerType::Add(Point3::new(1, 1, 0), "wod".to_string()));
+            tasks.add_world(TriggerType::Add(Point3::new(2, 1, 0), "wood".to_string()));
+        }
+        tick(&mut world);
+
+        // Misspelled objects get skipped w/o holding up the rest.
+        let map = world.read_resource::<Map>();
+        assert!(map.stack_at(&Point3::new(1, 1, 0)).is_none());
+        assert!(map.stack_at(&Point3::new(2, 1, 0)).is_some());
+    }
+
+    #[test]
+    fn test_take_conflict() {
+        let (mut world, mut reader) = setup(4, 4);
+
+        let item_pos = Point3::new(2, 0, 0);
+        {
+            let mut tasks = world.write_resource::<TaskQueue>();
+            tasks.add_world(TriggerType::AddWorker(Point3::new(0, 0, 0)));
+            tasks.add_world(TriggerType::AddWorker(Point3::new(1, 0, 0)));
+            tasks.add_world(TriggerType::Add(item_pos, "wood".to_string()));
+        }
+        tick(&mut world);
+
+        let (first, second, item) = {
+            let map = world.read_resource::<Map>();
+            (
+                map.worker_at(Point3::new(0, 0, 0)).unwrap(),
+                map.worker_at(Point3::new(1, 0, 0)).unwrap(),
+                map.stack_at(&item_pos).unwrap().top().unwrap(),
+            )
+        };
+
+        // Second worker was on their way to pick up the item.
+        {
+            let mut workers = world.write_storage::<Worker>();
+            let worker = workers.get_mut(world.entities().entity(second)).unwrap();
+            worker.goal = Some(Goal::Haul {
+                target: item,
+                position: item_pos,
+                resource: "wood".to_string(),
+            });
+        }
+
+        {
+            let mut tasks = world.write_resource::<TaskQueue>();
+            for owner in [first, second].iter() {
+                tasks.add_world_from(
+                    *owner,
+                    TriggerType::Take {
+                        target: item,
+                        owner: *owner,
+                    },
+                );
+            }
+        }
+        tick(&mut world);
+
+        // First claimant wins, the other gives up on their goal.
+        let workers = world.read_storage::<Worker>();
+        let first = workers.get(world.entities().entity(first)).unwrap();
+        let second = workers.get(world.entities().entity(second)).unwrap();
+        let inventories = world.read_storage::<Inventory>();
+        let first_inventory = inventories.get(world.entities().entity(first.id)).unwrap();
+        let second_inventory = inventories.get(world.entities().entity(second.id)).unwrap();
+        assert_eq!(first_inventory.count("wood"), 1);
+        assert!(second_inventory.is_empty());
+        assert!(second.goal.is_none());
+
+        let picked_up: Vec<SimEvent> = events(&world, &mut reader)
+            .into_iter()
+            .filter(|event| matches!(event, SimEvent::ItemPickedUp { .. }))
+            .collect();
+        assert_eq!(
+            picked_up,
+            vec![SimEvent::ItemPickedUp {
+                item,
+                owner: first.id,
+                resource: "wood".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_move_to() {
+        let (mut world, mut reader) = setup(4, 4);
+
+        let tree_pos = Point3::new(1, 0, 0);
+        {
+            let mut tasks = world.write_resource::<TaskQueue>();
+            tasks.add_world(TriggerType::AddWorker(Point3::new(0, 0, 0)));
+            tasks.add_world(TriggerType::Add(tree_pos, "tree".to_string()));
+        }
+        tick(&mut world);
+
+        let worker = world
+            .read_resource::<Map>()
+            .worker_at(Point3::new(0, 0, 0))
+            .unwrap();
+        {
+            let mut tasks = world.write_resource::<TaskQueue>();
+            // Can't stand on top of a tree.
+            tasks.add_world(TriggerType::MoveTo {
+                worker,
+                position: tree_pos,
+            });
+            tasks.add_world(TriggerType::MoveTo {
+                worker,
+                position: Point3::new(2, 0, 0),
+            });
+        }
+        tick(&mut world);
+
+        let failed: Vec<SimEvent> = events(&world, &mut reader)
+            .into_iter()
+            .filter(|event| matches!(event, SimEvent::PathFailed { .. }))
+            .collect();
+        assert_eq!(
+            failed,
+            vec![SimEvent::PathFailed {
+                worker,
+                from: Point3::new(0, 0, 0),
+                to: tree_pos,
+            }]
+        );
+
+        // Path goes around the tree.
+        let workers = world.read_storage::<Worker>();
+        let worker = workers.get(world.entities().entity(worker)).unwrap();
+        match &worker.order {
+            Some(TriggerType::Move { path, .. }) => {
+                assert_eq!(path.first(), Some(&Point3::new(2, 0, 0)));
+                assert!(!path.contains(&tree_pos));
+            }
+            order => panic!("unexpected order {:?}", order),
+        }
+    }
+
+    #[test]
+    fn test_destroy_drops() {
+        let (mut world, mut reader) = setup(4, 4);
+
+        let tree_pos = Point3::new(1, 1, 0);
+        world
+            .write_resource::<TaskQueue>()
+            .add_world(TriggerType::Add(tree_pos, "tree".to_string()));
+        tick(&mut world);
+
+        let tree = world
+            .read_resource::<Map>()
+            .stack_at(&tree_pos)
+            .unwrap()
+            .solid
+            .unwrap();
+        // Several systems noticing the tree is gone shouldn't destroy it twice.
+        for _ in 0..2 {
+            world
+                .write_resource::<TaskQueue>()
+                .add_world(TriggerType::Destroy(tree));
+        }
+        tick(&mut world);
+        world
+            .write_resource::<TaskQueue>()
+            .add_world(TriggerType::Destroy(tree));
+        tick(&mut world);
+
+        let destroyed = events(&world, &mut reader)
+            .into_iter()
+            .filter(|event| matches!(event, SimEvent::ObjectDestroyed { .. }))
+            .count();
+        assert_eq!(destroyed, 1);
+
+        // Tree drops 2-3 wood, spread out around where it was.
+        let map = world.read_resource::<Map>();
+        let wood: Vec<u32> = map
+            .object_map
+            .values()
+            .flat_map(|stack| stack.items.iter().copied())
+            .collect();
+        assert!(wood.len() >= 2 && wood.len() <= 3);
+        assert_eq!(map.stack_at(&tree_pos).unwrap().items.len(), 1);
+    }
+
+    #[test]
+    fn test_construction() {
+        let (mut world, _) = setup(4, 4);
+
+        let site_pos = Point3::new(2, 2, 0);
+        {
+            let mut tasks = world.write_resource::<TaskQueue>();
+            tasks.add_world(TriggerType::AddWorker(Point3::new(1, 2, 0)));
+            // Second one overlaps the first & should be ignored.
+            for _ in 0..2 {
+                tasks.add_world(TriggerType::PlaceBlueprint {
+                    blueprint: "wall".to_string(),
+                    position: site_pos,
+                });
+            }
+        }
+        tick(&mut world);
+
+        let worker = world
+            .read_resource::<Map>()
+            .worker_at(Point3::new(1, 2, 0))
+            .unwrap();
+        let site = {
+            let entities = world.entities();
+            let sites = world.read_storage::<Construction>();
+            let placed: Vec<u32> = (&entities, &sites).join().map(|(e, _)| e.id()).collect();
+            assert_eq!(placed.len(), 1);
+            placed[0]
+        };
+
+        world
+            .write_storage::<Inventory>()
+            .get_mut(world.entities().entity(worker))
+            .unwrap()
+            .add(ItemStack::new("wood", 3, 2));
+        world
+            .write_resource::<TaskQueue>()
+            .add_world(TriggerType::Deliver {
+                owner: worker,
+                site,
+                resource: "wood".to_string(),
+            });
+        tick(&mut world);
+
+        // Only what the wall needs is handed over.
+        let inventory = world.read_storage::<Inventory>();
+        let carried = inventory.get(world.entities().entity(worker)).unwrap();
+        assert_eq!(carried.count("wood"), 1);
+        drop(inventory);
+
+        let work = world
+            .read_storage::<Construction>()
+            .get(world.entities().entity(site))
+            .unwrap()
+            .blueprint
+            .work;
+        for _ in 0..work {
+            world
+                .write_resource::<TaskQueue>()
+                .add_world(TriggerType::Build {
+                    worker,
+                    site,
+                    work: 1,
+                });
+            tick(&mut world);
+        }
+
+        let map = world.read_resource::<Map>();
+        assert!(!map.terrain.is_walkable(&site_pos));
+        assert!(world
+            .read_storage::<Construction>()
+            .get(world.entities().entity(site))
+            .is_none());
+    }
+
+    #[test]
+    fn test_construction_waits_for_creature() {
+        let (mut world, _) = setup(4, 4);
+
+        let site_pos = Point3::new(2, 2, 0);
+        {
+            let mut tasks = world.write_resource::<TaskQueue>();
+            tasks.add_world(TriggerType::AddWorker(Point3::new(1, 2, 0)));
+            tasks.add_world(TriggerType::PlaceBlueprint {
+                blueprint: "wall".to_string(),
+                position: site_pos,
+            });
+            tasks.add_world(TriggerType::AddCreature(site_pos, "deer".to_string()));
+        }
+        tick(&mut world);
+
+        let (worker, deer) = {
+            let map = world.read_resource::<Map>();
+            (
+                map.workers_at(&Point3::new(1, 2, 0))[0],
+                map.creature_at(&site_pos).unwrap(),
+            )
+        };
+        let (site, work) = {
+            let entities = world.entities();
+            let mut sites = world.write_storage::<Construction>();
+            let (entity, site) = (&entities, &mut sites).join().next().unwrap();
+            site.deliver("wood", 2);
+            (entity.id(), site.blueprint.work)
+        };
+
+        // Deer wandered onto the site, don't wall it in.
+        world
+            .write_resource::<TaskQueue>()
+            .add_world(TriggerType::Build { worker, site, work });
+        tick(&mut world);
+        assert!(world.read_resource::<Map>().terrain.is_walkable(&site_pos));
+        assert!(world
+            .read_storage::<Construction>()
+            .get(world.entities().entity(site))
+            .is_some());
+
+        {
+            let mut map = world.write_resource::<Map>();
+            map.move_creature(deer, site_pos, Point3::new(3, 3, 0));
+        }
+        world
+            .write_resource::<TaskQueue>()
+            .add_world(TriggerType::Build {
+                worker,
+                site,
+                work: 0,
+            });
+        tick(&mut world);
+        assert!(!world.read_resource::<Map>().terrain.is_walkable(&site_pos));
+    }
+
+    #[test]
+    fn test_farming() {
+        let mut terrain = TerrainChunk::new(4, 4);
+        for y in 0..4 {
+            for x in 0..4 {
+                terrain.set((x, y, 0), Some(Biome::GRASSLAND));
+            }
+        }
+        let (mut world, _) = setup_with(&terrain);
+
+        let field = Point3::new(2, 2, 1);
+        {
+            let crops = world.read_resource::<CropLibrary>();
+            let mut map = world.write_resource::<Map>();
+            let wheat = crops.get("wheat").unwrap();
+            map.designate_farm(field, Point3::new(3, 3, 1), "wheat", wheat);
+        }
+
+        world
+            .write_resource::<TaskQueue>()
+            .add_world(TriggerType::AddWorker(Point3::new(1, 2, 1)));
+        tick(&mut world);
+        let worker = world
+            .read_resource::<Map>()
+            .worker_at(Point3::new(1, 2, 1))
+            .unwrap();
+
+        // Nothing can be planted until the soil has been tilled.
+        let plant = TriggerType::Plant {
+            worker,
+            position: field,
+            crop: "wheat".to_string(),
+        };
+        world.write_resource::<TaskQueue>().add_world(plant.clone());
+        tick(&mut world);
+        assert!(world.read_resource::<Map>().stack_at(&field).is_none());
+
+        {
+            let mut tasks = world.write_resource::<TaskQueue>();
+            tasks.add_world(TriggerType::Till {
+                worker,
+                position: field,
+            });
+            tasks.add_world(plant.clone());
+            // Only one crop per tile.
+            tasks.add_world(plant);
+        }
+        tick(&mut world);
+
+        let crop = {
+            let map = world.read_resource::<Map>();
+            assert!(map.farms.at(&field).unwrap().is_tilled(&field));
+            let stack = map.stack_at(&field).unwrap();
+            assert_eq!(stack.items.len(), 1);
+            assert!(!map.is_solid(&field));
+            stack.items[0]
+        };
+
+        // Can't pick it before it's grown.
+        let harvest = TriggerType::Harvest { worker, crop };
+        world
+            .write_resource::<TaskQueue>()
+            .add_world(harvest.clone());
+        tick(&mut world);
+        assert!(world.read_resource::<Map>().stack_at(&field).is_some());
+
+        {
+            let mut crops = world.write_storage::<Crop>();
+            let planted = crops.get_mut(world.entities().entity(crop)).unwrap();
+            planted.growth = planted.crop_type.growth;
+        }
+        world.write_resource::<TaskQueue>().add_world(harvest);
+        tick(&mut world);
+
+        let inventory = world.read_storage::<Inventory>();
+        let carried = inventory.get(world.entities().entity(worker)).unwrap();
+        assert_eq!(carried.count("wheat"), 3);
+
+        // Soil stays tilled for the next crop.
+        let map = world.read_resource::<Map>();
+        assert!(map.stack_at(&field).is_none());
+        assert!(map.farms.at(&field).unwrap().is_tilled(&field));
+    }
+
+    #[test]
+    fn test_combat() {
+        let (mut world, mut reader) = setup(4, 4);
+
+        let worker_pos = Point3::new(0, 0, 0);
+        {
+            let mut tasks = world.write_resource::<TaskQueue>();
+            tasks.add_world(TriggerType::AddWorker(worker_pos));
+            tasks.add_world(TriggerType::AddCreature(
+                Point3::new(1, 0, 0),
+                "goblin".to_string(),
+            ));
+        }
+        tick(&mut world);
+
+        let worker = (&world.entities(), &world.read_storage::<Worker>())
+            .join()
+            .map(|(entity, _)| entity.id())
+            .next()
+            .unwrap();
+        let goblin = (&world.entities(), &world.read_storage::<Creature>())
+            .join()
+            .map(|(entity, _)| entity.id())
+            .next()
+            .unwrap();
+        let job = {
+            let mut board = world.write_resource::<JobBoard>();
+            let job = board
+                .post(
+                    TriggerType::StartJob("clear_trees".to_string()),
+                    TriggerPriority::MEDIUM,
+                    None,
+                )
+                .unwrap();
+            board.claim(job, worker);
+            job
+        };
+
+        // Goblin lands a hit, the worker misses.
+        {
+            let mut tasks = world.write_resource::<TaskQueue>();
+            tasks.add_world_from(
+                goblin,
+                TriggerType::Attack {
+                    target: worker,
+                    source: goblin,
+                    damage: 5,
+                    accuracy: 100,
+                },
+            );
+            tasks.add_world_from(
+                worker,
+                TriggerType::Attack {
+                    target: goblin,
+                    source: worker,
+                    damage: 20,
+                    accuracy: 0,
+                },
+            );
+        }
+        tick(&mut world);
+
+        let sent = events(&world, &mut reader);
+        assert!(sent.contains(&SimEvent::Attacked {
+            attacker: goblin,
+            target: worker,
+            damage: Some(5),
+        }));
+        assert!(sent.contains(&SimEvent::Attacked {
+            attacker: worker,
+            target: goblin,
+            damage: None,
+        }));
+        {
+            let workers = world.read_storage::<Worker>();
+            let health = &workers.get(world.entities().entity(worker)).unwrap().health;
+            assert_eq!(health.hp, health.max - 5.0);
+            assert_eq!(health.last_attacker, Some(goblin));
+        }
+
+        // Dead worker leaves behind a corpse, their axe & their job.
+        world
+            .write_resource::<TaskQueue>()
+            .add_world(TriggerType::Die(worker));
+        tick(&mut world);
+
+        let sent = events(&world, &mut reader);
+        assert!(sent.contains(&SimEvent::WorkerDied {
+            worker,
+            position: worker_pos,
+            killer: Some(goblin),
+        }));
+        assert_eq!(world.read_storage::<Worker>().join().count(), 0);
+        assert_eq!(
+            world.read_resource::<JobBoard>().get(job).unwrap().state,
+            JobState::Open
+        );
+
+        let map = world.read_resource::<Map>();
+        assert!(map.workers_at(&worker_pos).is_empty());
+        let objects = world.read_storage::<MapObject>();
+        let left_behind: Vec<&str> = objects
+            .join()
+            .map(|object| object.resource_type.name.as_str())
+            .collect();
+        assert!(left_behind.contains(&"corpse"));
+        assert!(left_behind.contains(&"axe"));
+    }
+
+    #[test]
+    fn test_butchering() {
+        let (mut world, mut reader) = setup(4, 4);
+        let deer_pos = Point3::new(2, 0, 0);
+        {
+            let mut tasks = world.write_resource::<TaskQueue>();
+            tasks.add_world(TriggerType::AddWorker(Point3::new(1, 0, 0)));
+            tasks.add_world(TriggerType::AddCreature(deer_pos, "deer".to_string()));
+            tasks.add_world(TriggerType::Add(
+                Point3::new(1, 1, 0),
+                "butcher".to_string(),
+            ));
+        }
+        tick(&mut world);
+
+        let worker = world
+            .read_resource::<Map>()
+            .worker_at(Point3::new(1, 0, 0))
+            .unwrap();
+        let deer = (&world.entities(), &world.read_storage::<Creature>())
+            .join()
+            .map(|(entity, _)| entity.id())
+            .next()
+            .unwrap();
+        let workshop = (&world.entities(), &world.read_storage::<MapObject>())
+            .join()
+            .find(|(_, object)| object.resource_type.name == "butcher")
+            .map(|(entity, _)| entity.id())
+            .unwrap();
+
+        // Hunted down animals leave a carcass behind.
+        world
+            .write_resource::<TaskQueue>()
+            .add_world(TriggerType::Die(deer));
+        tick(&mut world);
+        let carcass = {
+            let map = world.read_resource::<Map>();
+            assert!(map.creature_at(&deer_pos).is_none());
+            map.stack_at(&deer_pos).unwrap().top().unwrap()
+        };
+        assert_eq!(
+            world
+                .read_storage::<MapObject>()
+                .get(world.entities().entity(carcass))
+                .map(|object| object.resource_type.name.as_str()),
+            Some("carcass")
+        );
+
+        {
+            let mut tasks = world.write_resource::<TaskQueue>();
+            tasks.add_world(TriggerType::Take {
+                target: carcass,
+                owner: worker,
+            });
+            tasks.add_world(TriggerType::Craft {
+                worker,
+                workshop,
+                recipe: "meat".to_string(),
+            });
+        }
+        tick(&mut world);
+
+        // Butchered into meat & hide.
+        assert!(
+            events(&world, &mut reader).contains(&SimEvent::ItemsCrafted {
+                worker,
+                recipe: "meat".to_string(),
+            })
+        );
+        let inventories = world.read_storage::<Inventory>();
+        let carried = inventories.get(world.entities().entity(worker)).unwrap();
+        assert_eq!(carried.count("carcass"), 0);
+        assert_eq!(carried.count("meat"), 2);
+        assert_eq!(carried.count("hide"), 1);
+    }
+}
@@ -7,7 +7,7 @@
 use core::Point3;
 use libterrain::Path;
 
-type EntityId = u32;
+pub type EntityId = u32;
 type MapPosition = Point3<u32>;
 
 #[derive(Debug, Clone, Eq, Hash, PartialEq)]
@@ -18,10 +18,22 @@ pub enum TriggerType {
         source: EntityId,
         damage: i32,
     },
+    /// Swing at a worker or creature, landing a hit <accuracy>% of the time.
+    Attack {
+        target: EntityId,
+        source: EntityId,
+        damage: i32,
+        accuracy: u32,
+    },
+    /// A worker or creature dies, leaving behind a corpse & anything it was
+    /// carrying.
+    Die(EntityId),
     /// Adds a new entity to the world.
     Add(MapPosition, String),
     /// Adds a new worker to the world.
     AddWorker(MapPosition),
+    /// Adds a new creature of some species to the world, e.g. "goblin".
+    AddCreature(MapPosition, String),
     /// Destroys entities and drops items. Should only be handled by the World.
     Destroy(EntityId),
     // Harvest a resource, e.g. chopping wood.
@@ -30,23 +42,127 @@ pub enum TriggerType {
         position: MapPosition,
         resource: String,
     },
+    /// Track down & kill an animal for its carcass.
+    Hunt { target: EntityId },
+    /// Have the next available worker start a job, e.g. "gather_wood".
+    StartJob(String),
+    /// Remove an item from the owner's inventory & destroy it, e.g. eating food.
+    Consume { owner: EntityId, resource: String },
+    /// Drop <count> of <resource> from the owner's inventory onto the map.
+    Drop {
+        owner: EntityId,
+        resource: String,
+        count: u32,
+        position: MapPosition,
+    },
     /// Take an object and place into inventory.
     /// NOTE: No checks are made to see if the entity is actually nearby or not.
     Take { target: EntityId, owner: EntityId },
-    /// Move along path
-    Move(Path),
-    /// Move to some location.
-    MoveTo(MapPosition),
+    /// Place a construction site for <blueprint> w/ its corner at <position>.
+    PlaceBlueprint {
+        blueprint: String,
+        position: MapPosition,
+    },
+    /// Hand over any <resource> the owner is carrying that the construction
+    /// site still needs.
+    Deliver {
+        owner: EntityId,
+        site: EntityId,
+        resource: String,
+    },
+    /// Put <work> into a construction site.
+    Build {
+        worker: EntityId,
+        site: EntityId,
+        work: u32,
+    },
+    /// Use up the inputs the worker is carrying to make <recipe>.
+    Craft {
+        worker: EntityId,
+        workshop: EntityId,
+        recipe: String,
+    },
+    /// Till the soil at <position> in a farm plot so it can be planted.
+    Till {
+        worker: EntityId,
+        position: MapPosition,
+    },
+    /// Plant <crop> on a tilled tile.
+    Plant {
+        worker: EntityId,
+        position: MapPosition,
+        crop: String,
+    },
+    /// Pick a fully grown crop, putting what it yields in the worker's
+    /// inventory.
+    Harvest { worker: EntityId, crop: EntityId },
+    /// Equip the tool <target> that's lying on the map.
+    Equip { worker: EntityId, target: EntityId },
+    /// Order a worker to follow a path, one step per tick.
+    Move { worker: EntityId, path: Path },
+    /// Order a worker to move to some location.
+    MoveTo {
+        worker: EntityId,
+        position: MapPosition,
+    },
+}
+
+impl TriggerType {
+    /// Entity this trigger acts on, if any.
+    pub fn target(&self) -> Option<EntityId> {
+        match self {
+            TriggerType::DealDamage { target, .. }
+            | TriggerType::Attack { target, .. }
+            | TriggerType::HarvestResource { target, .. }
+            | TriggerType::Hunt { target }
+            | TriggerType::Take { target, .. } => Some(*target),
+            TriggerType::Deliver { site, .. } | TriggerType::Build { site, .. } => Some(*site),
+            TriggerType::Craft { workshop, .. } => Some(*workshop),
+            TriggerType::Harvest { crop, .. } => Some(*crop),
+            TriggerType::Equip { target, .. } => Some(*target),
+            TriggerType::Destroy(target) | TriggerType::Die(target) => Some(*target),
+            _ => None,
+        }
+    }
+
+    /// Default priority when processing world updates.
+    /// - New entities are added first so they can be acted on in the same tick.
+    /// - Destroyed entities are removed before anything else can act on them.
+    pub fn priority(&self) -> TriggerPriority {
+        match self {
+            TriggerType::Add(..)
+            | TriggerType::AddWorker(_)
+            | TriggerType::AddCreature(..)
+            | TriggerType::Destroy(_)
+            | TriggerType::Die(_) => TriggerPriority::HIGH,
+            _ => TriggerPriority::MEDIUM,
+        }
+    }
 }
 
+/// Ordered from lowest to highest priority.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum TriggerPriority {
     LOW,
     MEDIUM,
     HIGH,
 }
 
+#[derive(Clone, Debug)]
 pub struct TriggerRecord {
     pub trigger: TriggerType,
-    pub source: usize,
+    /// Entity that caused this trigger, if any. They'll be notified if the
+    /// trigger fails.
+    pub source: Option<EntityId>,
     pub priority: TriggerPriority,
 }
+
+impl TriggerRecord {
+    pub fn new(trigger: TriggerType, source: Option<EntityId>) -> Self {
+        TriggerRecord {
+            priority: trigger.priority(),
+            trigger,
+            source,
+        }
+    }
+}
@@ -1,44 +1,358 @@
-use core::amethyst::ecs::{World, WorldExt};
+use core::amethyst::{
+    ecs::{World, WorldExt},
+    shrev::EventChannel,
+};
+use std::path::{Path, PathBuf};
 
 use crate::{
-    components::{EntityInfo, MapObject, Worker},
-    config::{ResourceConfig, WorldConfig},
+    buildings::BlueprintLibrary,
+    components::{Construction, Creature, Crop, EntityInfo, Inventory, MapObject, Worker},
+    config::{ConfigPaths, ResourceConfig, WorldConfig},
+    creatures::CreatureLibrary,
+    crops::CropLibrary,
+    error::Error,
+    events::SimEvent,
+    jobs::JobLibrary,
     planner::Planner,
-    resources::{time, Map, TaskQueue},
+    recipes::RecipeBook,
+    resources::{
+        time, Calendar, Climate, JobBoard, Map, ProductionQueue, SimRng, TaskQueue, Weather,
+    },
 };
 
 use libterrain::TerrainChunk;
 
+/// Paths to each data file, relative to the data directory.
+const ACTIONS_PATH: &str = "data/actions.ron";
+const BUILDINGS_PATH: &str = "data/buildings.ron";
+const CREATURES_PATH: &str = "data/creatures.ron";
+const CROPS_PATH: &str = "data/crops.ron";
+const JOBS_PATH: &str = "data/jobs.ron";
+const RECIPES_PATH: &str = "data/recipes.ron";
+const RESOURCES_PATH: &str = "data/resources.ron";
+const SIM_CONFIG_PATH: &str = "sim_config.ron";
+
 #[derive(Default)]
 pub struct WorldSim;
 impl WorldSim {
-    pub fn new(world: &mut World, terrain: &TerrainChunk, width: u32, height: u32) -> Self {
+    pub fn builder() -> WorldSimBuilder {
+        WorldSimBuilder::default()
+    }
+}
+
+/// Sets up the resources & components needed to run the simulation.
+///
+/// By default, the planner, jobs, blueprints, recipes, crops, creatures, resource and sim
+/// configs are loaded from the `./resources` directory. Use `with_data_dir` to load them from somewhere
+/// else, or pass in the configs directly to skip loading them from disk.
+pub struct WorldSimBuilder {
+    data_dir: PathBuf,
+    planner: Option<Planner>,
+    jobs: Option<JobLibrary>,
+    blueprints: Option<BlueprintLibrary>,
+    recipes: Option<RecipeBook>,
+    crops: Option<CropLibrary>,
+    creatures: Option<CreatureLibrary>,
+    resources: Option<ResourceConfig>,
+    config: Option<WorldConfig>,
+    seed: Option<u64>,
+}
+
+impl Default for WorldSimBuilder {
+    fn default() -> Self {
+        WorldSimBuilder {
+            data_dir: PathBuf::from("./resources"),
+            planner: None,
+            jobs: None,
+            blueprints: None,
+            recipes: None,
+            crops: None,
+            creatures: None,
+            resources: None,
+            config: None,
+            seed: None,
+        }
+    }
+}
+
+impl WorldSimBuilder {
+    /// Directory to load any data files from.
+    pub fn with_data_dir<P: AsRef<Path>>(mut self, data_dir: P) -> Self {
+        self.data_dir = data_dir.as_ref().to_path_buf();
+        self
+    }
+
+    pub fn with_planner(mut self, planner: Planner) -> Self {
+        self.planner = Some(planner);
+        self
+    }
+
+    pub fn with_jobs(mut self, jobs: JobLibrary) -> Self {
+        self.jobs = Some(jobs);
+        self
+    }
+
+    pub fn with_blueprints(mut self, blueprints: BlueprintLibrary) -> Self {
+        self.blueprints = Some(blueprints);
+        self
+    }
+
+    pub fn with_recipes(mut self, recipes: RecipeBook) -> Self {
+        self.recipes = Some(recipes);
+        self
+    }
+
+    pub fn with_crops(mut self, crops: CropLibrary) -> Self {
+        self.crops = Some(crops);
+        self
+    }
+
+    pub fn with_creatures(mut self, creatures: CreatureLibrary) -> Self {
+        self.creatures = Some(creatures);
+        self
+    }
+
+    pub fn with_resources(mut self, resources: ResourceConfig) -> Self {
+        self.resources = Some(resources);
+        self
+    }
+
+    pub fn with_config(mut self, config: WorldConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Seed the sim's random number generator. A random seed is used if
+    /// none is given.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn build(
+        self,
+        world: &mut World,
+        terrain: &TerrainChunk,
+        width: u32,
+        height: u32,
+    ) -> Result<WorldSim, Error> {
+        world.register::<Construction>();
+        world.register::<Creature>();
+        world.register::<Crop>();
         world.register::<EntityInfo>();
+        world.register::<Inventory>();
         world.register::<MapObject>();
         world.register::<Worker>();
 
+        // Keep track of where everything was loaded from so we can hot-reload
+        // these files.
+        let mut paths = ConfigPaths::default();
+
         // Initialize planner
-        let planner = Planner::load("./resources/data/actions.ron");
-        world.insert(planner);
+        let planner = match self.planner {
+            Some(planner) => planner,
+            None => {
+                let path = self.data_dir.join(ACTIONS_PATH);
+                let planner = Planner::load(&path)?;
+                paths.actions = Some(path);
+                planner
+            }
+        };
+
+        // Load job definitions
+        let jobs = match self.jobs {
+            Some(jobs) => jobs,
+            None => {
+                let path = self.data_dir.join(JOBS_PATH);
+                let jobs = JobLibrary::load(&path)?;
+                paths.jobs = Some(path);
+                jobs
+            }
+        };
+
+        // Load building blueprints
+        let blueprints = match self.blueprints {
+            Some(blueprints) => blueprints,
+            None => {
+                let path = self.data_dir.join(BUILDINGS_PATH);
+                let blueprints = BlueprintLibrary::load(&path)?;
+                paths.buildings = Some(path);
+                blueprints
+            }
+        };
+
+        // Load crafting recipes
+        let recipes = match self.recipes {
+            Some(recipes) => recipes,
+            None => {
+                let path = self.data_dir.join(RECIPES_PATH);
+                let recipes = RecipeBook::load(&path)?;
+                paths.recipes = Some(path);
+                recipes
+            }
+        };
+
+        // Load crop definitions
+        let crops = match self.crops {
+            Some(crops) => crops,
+            None => {
+                let path = self.data_dir.join(CROPS_PATH);
+                let crops = CropLibrary::load(&path)?;
+                paths.crops = Some(path);
+                crops
+            }
+        };
+
+        // Load creature species
+        let creatures = match self.creatures {
+            Some(creatures) => creatures,
+            None => {
+                let path = self.data_dir.join(CREATURES_PATH);
+                let creatures = CreatureLibrary::load(&path)?;
+                paths.creatures = Some(path);
+                creatures
+            }
+        };
 
         // Load resource configs
-        let resources = ResourceConfig::load("./resources/data/resources.ron");
-        world.insert(resources);
+        let resources = match self.resources {
+            Some(resources) => resources,
+            None => {
+                let path = self.data_dir.join(RESOURCES_PATH);
+                let resources = ResourceConfig::load(&path)?;
+                paths.resources = Some(path);
+                resources
+            }
+        };
 
         // Load sim config
-        let world_config = WorldConfig::load("./resources/sim_config.ron");
+        let world_config = match self.config {
+            Some(config) => config,
+            None => {
+                let path = self.data_dir.join(SIM_CONFIG_PATH);
+                let config = WorldConfig::load(&path)?;
+                paths.sim_config = Some(path);
+                config
+            }
+        };
+
+        world.insert(planner);
+        world.insert(jobs);
+        world.insert(blueprints);
+        world.insert(recipes);
+        world.insert(crops);
+        world.insert(creatures);
+        world.insert(resources);
         world.insert(world_config);
+        world.insert(paths);
 
         // Initialize map.
-        let map = Map::initialize(world, terrain, width, height);
+        let map = Map::initialize(world, terrain, width, height)?;
+        let weather = Weather::new(
+            Climate::of_map(&map),
+            &world.read_resource::<WorldConfig>().weather,
+        );
+        world.insert(weather);
         world.insert(map);
 
         // Initialize task queue.
         world.insert(TaskQueue::default());
+        world.insert(JobBoard::default());
+        world.insert(ProductionQueue::default());
+        // Outcomes of the simulation for the game to react to.
+        world.insert(EventChannel::<SimEvent>::new());
+        world.insert(match self.seed {
+            Some(seed) => SimRng::new(seed),
+            None => SimRng::default(),
+        });
         // Add time tracking resources
         world.insert(time::Time::default());
         world.insert(time::Stopwatch::default());
+        let calendar = Calendar::new(&world.read_resource::<WorldConfig>().calendar);
+        world.insert(calendar);
+
+        Ok(WorldSim)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::WorldSim;
+    use crate::{
+        buildings::BlueprintLibrary,
+        config::{
+            CalendarConfig, ConfigPaths, HealthConfig, NeedsConfig, ResourceConfig, WeatherConfig,
+            WorldConfig,
+        },
+        creatures::CreatureLibrary,
+        crops::CropLibrary,
+        error::Error,
+        jobs::JobLibrary,
+        planner::Planner,
+        recipes::RecipeBook,
+        resources::Map,
+    };
+    use core::amethyst::ecs::{World, WorldExt};
+    use libterrain::TerrainChunk;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_build_from_data_dir() {
+        let mut world = World::new();
+        let terrain = TerrainChunk::new(4, 4);
+        WorldSim::builder()
+            .with_data_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/../resources"))
+            .build(&mut world, &terrain, 4, 4)
+            .unwrap();
+
+        assert_eq!(world.read_resource::<Map>().width, 4);
+        assert!(world.read_resource::<ConfigPaths>().actions.is_some());
+    }
+
+    #[test]
+    fn test_build_in_memory() {
+        let mut world = World::new();
+        let terrain = TerrainChunk::new(4, 4);
+        WorldSim::builder()
+            // Should never be touched since everything is passed in.
+            .with_data_dir("/does/not/exist")
+            .with_planner(Planner::new())
+            .with_jobs(JobLibrary::default())
+            .with_blueprints(BlueprintLibrary::default())
+            .with_recipes(RecipeBook::default())
+            .with_crops(CropLibrary::default())
+            .with_creatures(CreatureLibrary::default())
+            .with_resources(ResourceConfig {
+                map: HashMap::new(),
+            })
+            .with_config(WorldConfig {
+                action_cost: 1.0,
+                worker_stamina: 1.0,
+                stock_targets: HashMap::new(),
+                carry_weight: 10,
+                starting_tools: Vec::new(),
+                needs: NeedsConfig::default(),
+                calendar: CalendarConfig::default(),
+                weather: WeatherConfig::default(),
+                health: HealthConfig::default(),
+            })
+            .build(&mut world, &terrain, 4, 4)
+            .unwrap();
+
+        assert!(world.read_resource::<ConfigPaths>().actions.is_none());
+    }
+
+    #[test]
+    fn test_build_missing_data_dir() {
+        let mut world = World::new();
+        let terrain = TerrainChunk::new(4, 4);
+        let result = WorldSim::builder()
+            .with_data_dir("/does/not/exist")
+            .build(&mut world, &terrain, 4, 4);
 
-        Default::default()
+        match result {
+            Err(Error::Io { .. }) => {}
+            _ => panic!("Expected an Io error"),
+        }
     }
 }
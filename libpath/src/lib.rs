@@ -32,12 +32,30 @@ impl PartialOrd for State {
 pub fn find_path<IN, N, FH, FN>(
     start: N,
     goal: N,
+    heuristic: FH,
+    find_neighbors: FN,
+) -> (IndexMap<N, (usize, usize)>, Vec<N>)
+where
+    N: Clone + Debug + Eq + Hash,
+    IN: IntoIterator<Item = (N, usize)>,
+    FH: FnMut(&N) -> usize,
+    FN: FnMut(&N) -> IN,
+{
+    find_path_with(start, |node| *node == goal, heuristic, find_neighbors)
+}
+
+/// Same as `find_path`, but the search ends at the first node where <is_goal>
+/// returns true. Useful when there are multiple nodes that would satisfy the goal.
+pub fn find_path_with<IN, N, FG, FH, FN>(
+    start: N,
+    mut is_goal: FG,
     mut heuristic: FH,
     mut find_neighbors: FN,
 ) -> (IndexMap<N, (usize, usize)>, Vec<N>)
 where
     N: Clone + Debug + Eq + Hash,
     IN: IntoIterator<Item = (N, usize)>,
+    FG: FnMut(&N) -> bool,
     FH: FnMut(&N) -> usize,
     FN: FnMut(&N) -> IN,
 {
@@ -51,11 +69,13 @@ where
     let mut parents: IndexMap<N, (usize, usize)> = IndexMap::new();
     // Node -> (parent node index, cost)
     // The parent node is which node visited this node w/ the least cost.
-    parents.insert(start, (0, std::usize::MAX));
+    parents.insert(start, (0, 0));
 
-    while let Some(State { cost, position }) = frontier.pop() {
-        let (node, &(_parent, _path_cost)) = parents.get_index(position).unwrap();
-        if *node == goal {
+    // <cost> includes the heuristic, so go by the cost of the path so far
+    // when moving on to the neighbors.
+    while let Some(State { position, .. }) = frontier.pop() {
+        let (node, &(_parent, path_cost)) = parents.get_index(position).unwrap();
+        if is_goal(node) {
             // Reconstruct path and return it.
             let mut path = Vec::new();
 
@@ -69,7 +89,7 @@ where
         }
 
         for (neighbor, move_cost) in find_neighbors(&node) {
-            let new_cost = cost + move_cost;
+            let new_cost = path_cost + move_cost;
 
             if !parents.contains_key(&neighbor) || parents.get(&neighbor).unwrap().1 > new_cost {
                 // Insert / update the current path & path cost.
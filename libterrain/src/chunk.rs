@@ -1,8 +1,9 @@
 use super::Point3;
+use serde::Deserialize;
 use std::collections::HashMap;
 
 // TODO: Load from config file
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Deserialize, PartialEq)]
 pub enum Biome {
     // Above ground biomes
     OCEAN,
@@ -41,6 +42,11 @@ impl TerrainChunk {
         }
     }
 
+    /// Number of z-levels in this chunk.
+    pub fn depth(&self) -> u32 {
+        ZLEVELS
+    }
+
     pub fn heuristic(a: &Point3<u32>, b: &Point3<u32>) -> usize {
         (a.x as i32 - b.x as i32).abs() as usize
             + (a.y as i32 - b.y as i32).abs() as usize
@@ -1,9 +1,13 @@
-use crate::game::{components::Direction, config::GameConfig, sprite::SpriteSheetStorage};
+use crate::game::{
+    components::{Direction, Object},
+    config::GameConfig,
+    sprite::SpriteSheetStorage,
+};
 
 use core::amethyst::{
     core::{math::Point3, transform::Transform, Hidden},
     prelude::*,
-    renderer::{SpriteRender, Transparent},
+    renderer::{resources::Tint, SpriteRender, Transparent},
 };
 use std::f32::consts::{FRAC_PI_2, PI};
 
@@ -75,7 +79,10 @@ impl MapRenderer {
                             })
                             // Rendered position
                             .with(map_render.place(&pt, 0.0))
-                            .with(Transparent);
+                            .with(Transparent)
+                            // Shaded by the time of day, see `DaylightSystem`.
+                            .with(Object)
+                            .with(Tint::default());
 
                         if !terrain.is_visible(x as u32, y as u32, z as u32) {
                             block = block.with(Hidden);
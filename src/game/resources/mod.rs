@@ -1,2 +1,4 @@
 mod map;
+mod notifications;
 pub use map::*;
+pub use notifications::*;
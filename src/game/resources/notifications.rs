@@ -0,0 +1,17 @@
+use std::collections::VecDeque;
+
+/// Number of notifications to keep around.
+const MAX_NOTIFICATIONS: usize = 10;
+
+/// Recent happenings in the simulation to show to the player, newest first.
+#[derive(Default)]
+pub struct Notifications {
+    pub messages: VecDeque<String>,
+}
+
+impl Notifications {
+    pub fn push(&mut self, message: String) {
+        self.messages.push_front(message);
+        self.messages.truncate(MAX_NOTIFICATIONS);
+    }
+}
@@ -1,6 +1,6 @@
 use core::{
-    amethyst::{ecs::Write, prelude::*},
-    log::info,
+    amethyst::{ecs::Write, prelude::*, renderer::resources::Tint, utils::application_root_dir},
+    log::{error, info},
     Point3,
 };
 /// Loading/initialization state.
@@ -22,11 +22,15 @@ use libterrain::TerrainGenerator;
 
 pub struct InitState {
     finished: bool,
+    failed: bool,
 }
 
 impl Default for InitState {
     fn default() -> InitState {
-        InitState { finished: false }
+        InitState {
+            finished: false,
+            failed: false,
+        }
     }
 }
 
@@ -36,6 +40,7 @@ impl SimpleState for InitState {
         world.register::<Cursor>();
         world.register::<Object>();
         world.register::<Player>();
+        world.register::<Tint>();
 
         let storage = SpriteSheetStorage::new(world);
         world.insert(storage);
@@ -52,7 +57,21 @@ impl SimpleState for InitState {
         let terrain_gen = TerrainGenerator::new(map_width, map_height).build();
         info!("Terrain gen took: {}ms", now.elapsed().unwrap().as_millis());
 
-        WorldSim::new(world, &terrain_gen.get_terrain(), map_width, map_height);
+        let data_dir = application_root_dir()
+            .expect("Failed to find application root")
+            .join("resources");
+        let sim = WorldSim::builder().with_data_dir(data_dir).build(
+            world,
+            &terrain_gen.get_terrain(),
+            map_width,
+            map_height,
+        );
+
+        if let Err(e) = sim {
+            error!("Failed to initialize simulation: {}", e);
+            self.failed = true;
+            return;
+        }
 
         // Render map
         let map_render = MapRenderer::initialize(world);
@@ -75,6 +94,10 @@ impl SimpleState for InitState {
     }
 
     fn update(&mut self, _data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+        if self.failed {
+            return Trans::Quit;
+        }
+
         if self.finished {
             return Trans::Switch(Box::new(RunningState::default()));
         }
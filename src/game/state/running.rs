@@ -13,12 +13,15 @@ use crate::game::{
     components::CameraFollow,
     resources::MapRenderer,
     systems::{
-        camera, debug, ui::debug::DebugUI, ClickSystem, CursorSystem, PlayerMovement,
-        RenderNPCSystem, RenderObjectSystem,
+        camera, debug, ui::debug::DebugUI, ClickSystem, CursorSystem, DaylightSystem,
+        NotificationSystem, PlayerMovement, RenderNPCSystem, RenderObjectSystem,
     },
 };
 use core::log;
-use libdwarf::WorldSimBundle;
+use libdwarf::{
+    resources::{Calendar, Weather},
+    WorldSimBundle,
+};
 
 pub struct RunningState<'a, 'b> {
     dispatcher: Option<Dispatcher<'a, 'b>>,
@@ -53,12 +56,19 @@ impl<'a, 'b> SimpleState for RunningState<'a, 'b> {
         // to them as they get added.
         dispatcher_builder.add(RenderObjectSystem, "render_obj_system", &["world_updates"]);
         dispatcher_builder.add(RenderNPCSystem, "render_npc_system", &["world_updates"]);
+        dispatcher_builder.add(DaylightSystem, "daylight", &["game_tick"]);
+        // Let the player know what's going on in the simulation.
+        dispatcher_builder.add(
+            NotificationSystem::default(),
+            "notifications",
+            &["world_updates"],
+        );
 
         let mut input_db = DispatcherBuilder::new();
         // Cursor selection
         input_db.add(CursorSystem, "cursor", &[]);
         // We handle click after the cursor is correctly transformed on the map.
-        input_db.add(ClickSystem, "click", &["cursor"]);
+        input_db.add(ClickSystem::default(), "click", &["cursor"]);
         // Moving around the map
         input_db.add(camera::CameraZoomSystem, "camera_zoom", &[]);
         input_db.add(camera::MapMovementSystem, "map_movement", &[]);
@@ -153,12 +163,12 @@ impl<'a, 'b> SimpleState for RunningState<'a, 'b> {
             dispatcher.dispatch(&world);
         }
 
-        // Update FPS counter
+        // Update FPS counter & date
         let mut fps_display = None;
+        let mut date_display = None;
         world.exec(|finder: UiFinder<'_>| {
-            if let Some(entity) = finder.find("fps") {
-                fps_display = Some(entity);
-            }
+            fps_display = finder.find("fps");
+            date_display = finder.find("date");
         });
 
         let mut ui_text = world.write_storage::<UiText>();
@@ -169,6 +179,14 @@ impl<'a, 'b> SimpleState for RunningState<'a, 'b> {
                     fps.text = format!("FPS: {:.*}", 2, fps_samp);
                 }
             }
+
+            if let Some(date) = date_display.and_then(|entity| ui_text.get_mut(entity)) {
+                date.text = format!(
+                    "{}, {}",
+                    *world.read_resource::<Calendar>(),
+                    *world.read_resource::<Weather>()
+                );
+            }
         }
 
         Trans::None
@@ -1,30 +1,35 @@
 use core::amethyst::{
     core::Transform,
-    ecs::{Entities, Join, Read, ReadExpect, ReadStorage, System, Write, WriteStorage},
+    ecs::{Entities, Join, Read, ReadStorage, System, Write, WriteExpect, WriteStorage},
     input::{InputHandler, StringBindings},
     renderer::rendy::wsi::winit::MouseButton,
 };
 
 use crate::game::components::{Cursor, CursorDown, CursorSelected};
-use core::log;
+use core::{log, Point3};
 use libdwarf::{
-    components::{EntityInfo, MapObject},
-    resources::TaskQueue,
-    trigger::TriggerType,
+    components::{EntityInfo, Labor, MapObject},
+    resources::{JobBoard, TaskQueue},
+    trigger::{TriggerPriority, TriggerType},
 };
 
-pub struct ClickSystem;
+#[derive(Default)]
+pub struct ClickSystem {
+    /// Was the right mouse button down last frame?
+    right_down: bool,
+}
 
 impl<'s> System<'s> for ClickSystem {
     type SystemData = (
         Entities<'s>,
         WriteStorage<'s, Cursor>,
         WriteStorage<'s, CursorDown>,
-        ReadExpect<'s, CursorSelected>,
+        WriteExpect<'s, CursorSelected>,
         ReadStorage<'s, MapObject>,
         ReadStorage<'s, EntityInfo>,
         Read<'s, InputHandler<StringBindings>>,
         WriteStorage<'s, Transform>,
+        Write<'s, JobBoard>,
         Write<'s, TaskQueue>,
     );
 
@@ -34,14 +39,34 @@ impl<'s> System<'s> for ClickSystem {
             entities,
             mut cursors,
             mut cursor_down,
-            cursor_selected,
+            mut cursor_selected,
             map_objects,
             map_pos,
             input,
             mut transforms,
-            mut task_queue,
+            mut job_board,
+            mut tasks,
         ): Self::SystemData,
     ) {
+        // Right click orders the selected worker to move to the tile under
+        // the cursor.
+        let right_is_down = input.mouse_button_is_down(MouseButton::Right);
+        if self.right_down && !right_is_down {
+            let worker = cursor_selected.pinned.as_ref().and_then(|pick| pick.worker);
+            let position = cursor_selected
+                .hover_selected
+                .as_ref()
+                .and_then(|pick| pick.position)
+                .filter(|pos| pos.x >= 0 && pos.y >= 0 && pos.z >= 0);
+
+            if let (Some(worker), Some(pos)) = (worker, position) {
+                let position = Point3::new(pos.x as u32, pos.y as u32, pos.z as u32);
+                log::debug!("ordering worker {} to {:?}", worker, position);
+                tasks.add_world(TriggerType::MoveTo { worker, position });
+            }
+        }
+        self.right_down = right_is_down;
+
         // Capture mouse down events.
         let cursor_is_down = (&*entities, &mut cursors, !&cursor_down)
             .join()
@@ -61,7 +86,12 @@ impl<'s> System<'s> for ClickSystem {
             .or(None);
         if let Some((entity, _, _, _)) = cursor_transform {
             if !input.mouse_button_is_down(MouseButton::Left) {
-                if let Some(pick) = &cursor_selected.hover_selected {
+                if let Some(pick) = cursor_selected.hover_selected.clone() {
+                    // Select workers so they can be given orders.
+                    if pick.worker.is_some() {
+                        cursor_selected.pinned = Some(pick.clone());
+                    }
+
                     if let Some(obj_entity) = pick.object {
                         let obj_info = map_objects.get(entities.entity(obj_entity));
                         let obj_pos = map_pos.get(entities.entity(obj_entity));
@@ -69,12 +99,20 @@ impl<'s> System<'s> for ClickSystem {
                         if let Some(info) = obj_info {
                             if let Some(pos) = obj_pos {
                                 log::debug!("click! {:?}", info);
-                                // Add to task queue
-                                task_queue.add(TriggerType::HarvestResource {
-                                    target: obj_entity,
-                                    position: pos.pos,
-                                    resource: String::from("wood"),
-                                });
+                                // Post to the job board
+                                let posted = job_board.post(
+                                    TriggerType::HarvestResource {
+                                        target: obj_entity,
+                                        position: pos.pos,
+                                        resource: String::from("wood"),
+                                    },
+                                    TriggerPriority::MEDIUM,
+                                    Some(Labor::Gathering),
+                                );
+
+                                if posted.is_none() {
+                                    log::debug!("{} already has a job", obj_entity);
+                                }
                             }
                         }
                     }
@@ -0,0 +1,30 @@
+use core::amethyst::{
+    ecs::{Join, ReadExpect, ReadStorage, System, WriteStorage},
+    renderer::{palette::Srgba, resources::Tint},
+};
+
+use libdwarf::resources::Calendar;
+
+use crate::game::components::Object;
+
+/// How dark the terrain gets in the dead of night, 0.0 being pitch black.
+const NIGHT_BRIGHTNESS: f32 = 0.35;
+
+/// Tints the terrain based on the time of day in the sim.
+pub struct DaylightSystem;
+impl<'a> System<'a> for DaylightSystem {
+    type SystemData = (
+        ReadStorage<'a, Object>,
+        WriteStorage<'a, Tint>,
+        ReadExpect<'a, Calendar>,
+    );
+
+    fn run(&mut self, (terrain, mut tints, calendar): Self::SystemData) {
+        let brightness = NIGHT_BRIGHTNESS + (1.0 - NIGHT_BRIGHTNESS) * calendar.daylight();
+        // Nights have a slight blue tinge to them.
+        let color = Srgba::new(brightness, brightness, brightness.max(0.5), 1.0);
+        for (_, tint) in (&terrain, &mut tints).join() {
+            tint.0 = color;
+        }
+    }
+}
@@ -1,7 +1,9 @@
 pub mod camera;
 mod click;
 mod cursor;
+mod daylight;
 pub mod debug;
+mod notifications;
 mod player_movement;
 mod render_npc;
 mod render_object;
@@ -9,6 +11,8 @@ pub mod ui;
 
 pub use click::*;
 pub use cursor::*;
+pub use daylight::*;
+pub use notifications::*;
 pub use player_movement::*;
 pub use render_npc::*;
 pub use render_object::*;
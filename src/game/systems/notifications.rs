@@ -0,0 +1,142 @@
+use core::amethyst::{
+    assets::{AssetStorage, Loader},
+    audio::{output::Output, OggFormat, Source, SourceHandle},
+    ecs::{Read, System, SystemData, World, Write},
+    shrev::{EventChannel, ReaderId},
+};
+
+use crate::game::resources::Notifications;
+use libdwarf::events::SimEvent;
+
+/// Volume used when playing notification sounds.
+const VOLUME: f32 = 0.5;
+
+/// Turns simulation events into notifications & sounds for the player.
+#[derive(Default)]
+pub struct NotificationSystem {
+    reader: Option<ReaderId<SimEvent>>,
+    boop: Option<SourceHandle>,
+}
+
+impl<'s> System<'s> for NotificationSystem {
+    type SystemData = (
+        Read<'s, EventChannel<SimEvent>>,
+        Write<'s, Notifications>,
+        Read<'s, AssetStorage<Source>>,
+        Option<Read<'s, Output>>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+        self.reader = Some(
+            world
+                .fetch_mut::<EventChannel<SimEvent>>()
+                .register_reader(),
+        );
+
+        let loader = world.read_resource::<Loader>();
+        self.boop = Some(loader.load(
+            "audio/boop.ogg",
+            OggFormat,
+            (),
+            &world.read_resource::<AssetStorage<Source>>(),
+        ));
+    }
+
+    fn run(&mut self, (events, mut notifications, sources, output): Self::SystemData) {
+        let mut play_sound = false;
+        let reader = self
+            .reader
+            .as_mut()
+            .expect("NotificationSystem::setup registers the event reader");
+        for event in events.read(reader) {
+            let message = match event {
+                SimEvent::ObjectDestroyed {
+                    resource, position, ..
+                } => {
+                    play_sound = true;
+                    format!("{} destroyed @ ({}, {})", resource, position.x, position.y)
+                }
+                SimEvent::WorkerSpawned { id, .. } => format!("Worker {} has arrived", id),
+                SimEvent::JobCompleted { worker, task, .. } => {
+                    play_sound = true;
+                    format!("Worker {} finished {:?}", worker, task)
+                }
+                SimEvent::ConstructionFinished {
+                    blueprint,
+                    position,
+                    ..
+                } => {
+                    play_sound = true;
+                    format!("{} built @ ({}, {})", blueprint, position.x, position.y)
+                }
+                SimEvent::ItemsCrafted { worker, recipe } => {
+                    format!("Worker {} made {}", worker, recipe)
+                }
+                SimEvent::CropHarvested {
+                    worker,
+                    crop,
+                    position,
+                } => format!(
+                    "Worker {} harvested {} @ ({}, {})",
+                    worker, crop, position.x, position.y
+                ),
+                SimEvent::ToolBroke { worker, tool } => {
+                    format!("Worker {}'s {} broke", worker, tool)
+                }
+                SimEvent::SkillLevelUp {
+                    worker,
+                    skill,
+                    level,
+                } => format!("Worker {} is now level {} at {:?}", worker, level, skill),
+                SimEvent::WorkerCollapsed { worker } => {
+                    play_sound = true;
+                    format!("Worker {} collapsed from exhaustion", worker)
+                }
+                SimEvent::WorkerRecovered { worker } => {
+                    format!("Worker {} is back on their feet", worker)
+                }
+                SimEvent::WeatherChanged { weather } => format!("Weather: {}", weather),
+                SimEvent::CreatureSpawned {
+                    creature, position, ..
+                } => {
+                    play_sound = true;
+                    format!("A {} appeared @ ({}, {})", creature, position.x, position.y)
+                }
+                SimEvent::CreatureKilled {
+                    creature,
+                    killer: Some(killer),
+                    ..
+                } => format!("{} killed a {}", killer, creature),
+                SimEvent::CreatureKilled { creature, .. } => format!("A {} died", creature),
+                SimEvent::WorkerDied {
+                    worker, position, ..
+                } => {
+                    play_sound = true;
+                    format!("Worker {} died @ ({}, {})", worker, position.x, position.y)
+                }
+                SimEvent::Attacked {
+                    attacker,
+                    target,
+                    damage: Some(damage),
+                } => format!("{} hit {} for {}", attacker, target, damage),
+                // Happens too often to be worth mentioning.
+                SimEvent::Attacked { .. } => continue,
+                SimEvent::PathFailed { worker, to, .. } => {
+                    format!("Worker {} can't reach ({}, {})", worker, to.x, to.y)
+                }
+                // Happens too often to be worth mentioning.
+                SimEvent::ItemPickedUp { .. } => continue,
+            };
+
+            notifications.push(message);
+        }
+
+        if play_sound {
+            let sound = self.boop.as_ref().and_then(|boop| sources.get(boop));
+            if let (Some(output), Some(sound)) = (&output, sound) {
+                output.play_once(sound, VOLUME);
+            }
+        }
+    }
+}
@@ -1,10 +1,10 @@
 use core::amethyst::{
     core::transform::Transform,
     ecs::{Entities, Entity, Join, ReadExpect, ReadStorage, System, WriteStorage},
-    renderer::{SpriteRender, Transparent},
+    renderer::{palette::Srgba, resources::Tint, SpriteRender, Transparent},
 };
 
-use libdwarf::components::{EntityInfo, Worker};
+use libdwarf::components::{Creature, EntityInfo, Worker};
 
 use crate::game::{resources::MapRenderer, sprite::SpriteSheetStorage};
 
@@ -13,10 +13,12 @@ impl<'a> System<'a> for RenderNPCSystem {
     type SystemData = (
         Entities<'a>,
         WriteStorage<'a, Worker>,
+        ReadStorage<'a, Creature>,
         ReadStorage<'a, EntityInfo>,
         WriteStorage<'a, Transform>,
         WriteStorage<'a, SpriteRender>,
         WriteStorage<'a, Transparent>,
+        WriteStorage<'a, Tint>,
         ReadExpect<'a, MapRenderer>,
         ReadExpect<'a, SpriteSheetStorage>,
     );
@@ -26,10 +28,12 @@ impl<'a> System<'a> for RenderNPCSystem {
         (
             entities,
             mut workers,
+            creatures,
             positions,
             mut transforms,
             mut sprites,
             mut transparents,
+            mut tints,
             map_render,
             sheets,
         ): Self::SystemData,
@@ -59,6 +63,31 @@ impl<'a> System<'a> for RenderNPCSystem {
             transparents.insert(entity, Transparent).unwrap();
         }
 
+        // Creatures share the npc sprites, tinted red so they stand out.
+        let invisible: Vec<(Entity, &Creature, &EntityInfo, ())> =
+            (&*entities, &creatures, &positions, !&sprites)
+                .join()
+                .collect();
+
+        for (entity, creature, map_pos, _) in invisible {
+            transforms
+                .insert(entity, map_render.place(&map_pos.pos, 1.0))
+                .unwrap();
+            sprites
+                .insert(
+                    entity,
+                    SpriteRender {
+                        sprite_sheet: sheets.npc.clone(),
+                        sprite_number: creature.creature_type.sprite,
+                    },
+                )
+                .unwrap();
+            tints
+                .insert(entity, Tint(Srgba::new(1.0, 0.4, 0.4, 1.0)))
+                .unwrap();
+            transparents.insert(entity, Transparent).unwrap();
+        }
+
         // Update object positions
         for (_, map_pos, transform) in (&mut workers, &positions, &mut transforms).join() {
             let pos = map_pos.pos;
@@ -69,5 +98,14 @@ impl<'a> System<'a> for RenderNPCSystem {
                 new_transform.translation().z,
             );
         }
+
+        for (_, map_pos, transform) in (&creatures, &positions, &mut transforms).join() {
+            let new_transform = map_render.place(&map_pos.pos, 0.9);
+            transform.set_translation_xyz(
+                new_transform.translation().x,
+                new_transform.translation().y,
+                new_transform.translation().z,
+            );
+        }
     }
 }
@@ -4,7 +4,7 @@ use core::amethyst::{
     renderer::{SpriteRender, Transparent},
 };
 
-use libdwarf::components::{EntityInfo, MapObject};
+use libdwarf::components::{Crop, EntityInfo, MapObject};
 
 use crate::game::{resources::MapRenderer, sprite::SpriteSheetStorage};
 
@@ -14,6 +14,7 @@ impl<'a> System<'a> for RenderObjectSystem {
         Entities<'a>,
         WriteStorage<'a, MapObject>,
         ReadStorage<'a, EntityInfo>,
+        ReadStorage<'a, Crop>,
         WriteStorage<'a, Transform>,
         WriteStorage<'a, SpriteRender>,
         WriteStorage<'a, Transparent>,
@@ -27,6 +28,7 @@ impl<'a> System<'a> for RenderObjectSystem {
             entities,
             mut objects,
             positions,
+            crops,
             mut transforms,
             mut sprites,
             mut transparents,
@@ -58,6 +60,11 @@ impl<'a> System<'a> for RenderObjectSystem {
             transparents.insert(entity, Transparent).unwrap();
         }
 
+        // Keep crops in sync w/ their growth stage.
+        for (crop, sprite) in (&crops, &mut sprites).join() {
+            sprite.sprite_number = crop.sprite();
+        }
+
         // Remove sprites for hidden objects
         let needs_hiding: Vec<(Entity, &mut MapObject, ())> =
             (&*entities, &mut objects, !&positions).join().collect();
@@ -1,47 +1,205 @@
-use amethyst_imgui::imgui::{im_str, Condition, Window};
-use core::amethyst::ecs::{Entities, Join, ReadExpect, ReadStorage, System, Write};
+use amethyst_imgui::imgui::{im_str, Condition, ImString, Window};
+use core::amethyst::ecs::{
+    Entities, Join, Read, ReadExpect, ReadStorage, System, Write, WriteExpect, WriteStorage,
+};
 
 use core::Point3;
 use libdwarf::{
-    components::{MapObject, Worker},
-    resources::TaskQueue,
-    trigger::TriggerType,
+    buildings::BlueprintLibrary,
+    components::{Construction, Creature, Inventory, Labor, MapObject, Skill, Slot, Worker},
+    crops::CropLibrary,
+    jobs::JobLibrary,
+    recipes::RecipeBook,
+    resources::{JobBoard, Map, OrderKind, ProductionQueue, TaskQueue},
+    trigger::{TriggerPriority, TriggerType},
 };
 
-use crate::game::{components::CursorSelected, resources::MapRenderer};
+use crate::game::{
+    components::CursorSelected,
+    resources::{MapRenderer, Notifications},
+};
 
 #[derive(Default)]
 pub struct DebugUI {
     new_worker_pos: [i32; 3],
+    stockpile_from: [i32; 3],
+    stockpile_to: [i32; 3],
+    /// Comma separated list of resources the new stockpile accepts.
+    stockpile_accepts: ImString,
+    blueprint_pos: [i32; 3],
+    farm_from: [i32; 3],
+    farm_to: [i32; 3],
+    order_count: i32,
 }
 
 impl<'s> System<'s> for DebugUI {
     type SystemData = (
         Entities<'s>,
         ReadStorage<'s, MapObject>,
-        ReadStorage<'s, Worker>,
+        WriteStorage<'s, Worker>,
+        ReadStorage<'s, Inventory>,
+        ReadStorage<'s, Construction>,
+        ReadStorage<'s, Creature>,
         ReadExpect<'s, CursorSelected>,
         ReadExpect<'s, MapRenderer>,
+        WriteExpect<'s, Map>,
+        ReadExpect<'s, JobLibrary>,
+        ReadExpect<'s, BlueprintLibrary>,
+        ReadExpect<'s, RecipeBook>,
+        ReadExpect<'s, CropLibrary>,
         Write<'s, TaskQueue>,
+        Write<'s, JobBoard>,
+        Write<'s, ProductionQueue>,
+        Read<'s, Notifications>,
     );
 
     fn run(
         &mut self,
-        (entities, objects, workers, cursor_selected, map, mut queue): Self::SystemData,
+        (
+            entities,
+            objects,
+            mut workers,
+            inventories,
+            constructions,
+            creatures,
+            cursor_selected,
+            map,
+            mut world_map,
+            jobs,
+            blueprints,
+            recipes,
+            crops,
+            mut queue,
+            mut board,
+            mut production,
+            notifications,
+        ): Self::SystemData,
     ) {
         amethyst_imgui::with(|ui| {
             Window::new(im_str!("Tasks"))
                 .size([300.0, 500.0], Condition::FirstUseEver)
                 .build(ui, || {
                     if ui.collapsing_header(im_str!("world")).build() {
-                        for action in queue.world.iter() {
-                            ui.text(&im_str!("{:?}", action));
+                        for record in queue.world.iter() {
+                            ui.text(&im_str!("{:?} {:?}", record.priority, record.trigger));
+                        }
+                    }
+
+                    if ui.collapsing_header(im_str!("job board")).build() {
+                        for job in board.jobs().iter() {
+                            ui.text(&im_str!(
+                                "{:?} {:?}: {:?}",
+                                job.priority,
+                                job.state,
+                                job.task
+                            ));
                         }
                     }
 
-                    if ui.collapsing_header(im_str!("worker")).build() {
-                        for action in queue.worker.iter() {
-                            ui.text(&im_str!("{:?}", action));
+                    if ui.collapsing_header(im_str!("jobs")).build() {
+                        for job in jobs.job_ids() {
+                            if ui.button(&im_str!("{}", job), [0.0, 0.0]) {
+                                let labor = jobs.get(job).and_then(|job| job.labor);
+                                board.post(
+                                    TriggerType::StartJob(job.clone()),
+                                    TriggerPriority::MEDIUM,
+                                    labor,
+                                );
+                            }
+                        }
+                    }
+
+                    if ui.collapsing_header(im_str!("buildings")).build() {
+                        ui.input_int3(im_str!("build pos"), &mut self.blueprint_pos)
+                            .build();
+                        for blueprint in blueprints.blueprint_ids() {
+                            if ui.button(&im_str!("{}", blueprint), [0.0, 0.0]) {
+                                queue.add_world(TriggerType::PlaceBlueprint {
+                                    blueprint: blueprint.clone(),
+                                    position: Point3::new(
+                                        self.blueprint_pos[0] as u32,
+                                        self.blueprint_pos[1] as u32,
+                                        self.blueprint_pos[2] as u32,
+                                    ),
+                                });
+                            }
+                        }
+
+                        ui.separator();
+
+                        for (entity, site) in (&entities, &constructions).join() {
+                            ui.text(&im_str!(
+                                "Site {} ({}): {}/{} work, delivered {:?}",
+                                entity.id(),
+                                site.blueprint.name,
+                                site.progress,
+                                site.blueprint.work,
+                                site.delivered
+                            ));
+                        }
+                    }
+
+                    if ui.collapsing_header(im_str!("production")).build() {
+                        ui.input_int(im_str!("count"), &mut self.order_count)
+                            .build();
+                        let count = self.order_count.max(1) as u32;
+                        for recipe in recipes.recipe_ids() {
+                            if ui.button(&im_str!("make {}", recipe), [0.0, 0.0]) {
+                                production.add(recipe, OrderKind::Make(count));
+                            }
+                            ui.same_line(0.0);
+                            if ui.button(&im_str!("keep stocked##{}", recipe), [0.0, 0.0]) {
+                                production.add(recipe, OrderKind::KeepStocked(count));
+                            }
+                        }
+
+                        ui.separator();
+
+                        let mut removed = None;
+                        for order in production.orders().iter() {
+                            ui.text(&im_str!(
+                                "Order {}: {} {:?}",
+                                order.id,
+                                order.recipe,
+                                order.kind
+                            ));
+                            if ui.button(&im_str!("Remove##order{}", order.id), [0.0, 0.0]) {
+                                removed = Some(order.id);
+                            }
+                        }
+                        if let Some(id) = removed {
+                            production.remove(id);
+                        }
+                    }
+
+                    if ui.collapsing_header(im_str!("wildlife")).build() {
+                        let hunted: Vec<u32> = board.targets().collect();
+                        for (entity, creature) in (&entities, &creatures).join() {
+                            if creature.creature_type.is_hostile() {
+                                continue;
+                            }
+
+                            ui.text(&im_str!(
+                                "{} {}: {:.0}/{:.0} hp",
+                                creature.creature_type.name,
+                                entity.id(),
+                                creature.health.hp,
+                                creature.health.max
+                            ));
+                            if hunted.contains(&entity.id()) {
+                                continue;
+                            }
+
+                            ui.same_line(0.0);
+                            if ui.button(&im_str!("Hunt##{}", entity.id()), [0.0, 0.0]) {
+                                board.post(
+                                    TriggerType::Hunt {
+                                        target: entity.id(),
+                                    },
+                                    TriggerPriority::MEDIUM,
+                                    Some(Labor::Hunting),
+                                );
+                            }
                         }
                     }
                 });
@@ -62,12 +220,70 @@ impl<'s> System<'s> for DebugUI {
 
                     ui.separator();
 
-                    for (entity, worker) in (&entities, &workers).join() {
+                    for (entity, worker) in (&entities, &mut workers).join() {
                         if ui
                             .collapsing_header(&im_str!("Worker {}", entity.id()))
                             .build()
                         {
-                            ui.text(&im_str!("inventory: {}", worker.inventory.len()));
+                            if let Some(inventory) = inventories.get(entity) {
+                                ui.text(&im_str!(
+                                    "inventory: {}/{}",
+                                    inventory.weight(),
+                                    inventory.max_weight
+                                ));
+                                for stack in inventory.stacks.iter() {
+                                    ui.text(&im_str!("- {}", stack));
+                                }
+                            }
+                            for labor in Labor::ALL.iter() {
+                                let mut enabled = worker.can_do(Some(*labor));
+                                let label = im_str!("{:?}##{}", labor, entity.id());
+                                if ui.checkbox(&label, &mut enabled) {
+                                    if enabled {
+                                        worker.labors.insert(*labor);
+                                    } else {
+                                        worker.labors.remove(labor);
+                                    }
+                                }
+                            }
+                            for slot in Slot::ALL.iter() {
+                                if let Some(tool) = worker.equipment.get(*slot) {
+                                    ui.text(&im_str!("{:?}: {}", slot, tool));
+                                }
+                            }
+                            for skill in Skill::ALL.iter() {
+                                ui.text(&im_str!(
+                                    "{:?}: level {} ({} xp)",
+                                    skill,
+                                    worker.skills.level(*skill),
+                                    worker.skills.experience(*skill)
+                                ));
+                            }
+                            ui.text(&im_str!(
+                                "needs: hunger {:.2}, thirst {:.2}, rest {:.2}, social {:.2}",
+                                worker.needs.hunger,
+                                worker.needs.thirst,
+                                worker.needs.rest,
+                                worker.needs.social
+                            ));
+                            ui.text(&im_str!(
+                                "health: {:.0}/{:.0}, bleeding {:.2}",
+                                worker.health.hp,
+                                worker.health.max,
+                                worker.health.bleeding
+                            ));
+                            if worker.collapsed {
+                                ui.text(im_str!("collapsed"));
+                            }
+                            if let Some(job) = &worker.job {
+                                ui.text(&im_str!("{}", job));
+                            }
+                            if let Some(TriggerType::Move { path, .. }) = &worker.order {
+                                ui.text(&im_str!("order: move ({} steps left)", path.len()));
+                            }
+                            if let Some(goal) = &worker.goal {
+                                ui.text(&im_str!("goal: {:?}", goal));
+                            }
                             if ui.collapsing_header(im_str!("Action Queue")).build() {
                                 for action in worker.queue.iter() {
                                     ui.text(&im_str!("{:?}", action));
@@ -79,6 +295,112 @@ impl<'s> System<'s> for DebugUI {
                     }
                 });
 
+            Window::new(im_str!("Stockpiles"))
+                .size([300.0, 200.0], Condition::FirstUseEver)
+                .build(ui, || {
+                    ui.input_int3(im_str!("from"), &mut self.stockpile_from)
+                        .build();
+                    ui.input_int3(im_str!("to"), &mut self.stockpile_to).build();
+                    ui.input_text(im_str!("accepts"), &mut self.stockpile_accepts)
+                        .resize_buffer(true)
+                        .build();
+
+                    if ui.button(im_str!("Designate"), [0.0, 0.0]) {
+                        let from = self.stockpile_from;
+                        let to = self.stockpile_to;
+                        let accepts = self
+                            .stockpile_accepts
+                            .to_str()
+                            .split(',')
+                            .map(|name| name.trim().to_string())
+                            .filter(|name| !name.is_empty())
+                            .collect();
+                        world_map.stockpiles.designate(
+                            Point3::new(from[0] as u32, from[1] as u32, from[2] as u32),
+                            Point3::new(to[0] as u32, to[1] as u32, from[2] as u32),
+                            accepts,
+                        );
+                    }
+
+                    ui.separator();
+
+                    let mut removed = None;
+                    for pile in world_map.stockpiles.iter() {
+                        ui.text(&im_str!(
+                            "Stockpile {}: ({}, {}) to ({}, {}) {:?}",
+                            pile.id,
+                            pile.min.x,
+                            pile.min.y,
+                            pile.max.x,
+                            pile.max.y,
+                            pile.accepts
+                        ));
+                        if ui.button(&im_str!("Remove##{}", pile.id), [0.0, 0.0]) {
+                            removed = Some(pile.id);
+                        }
+                    }
+                    if let Some(id) = removed {
+                        world_map.stockpiles.remove(id);
+                    }
+
+                    ui.separator();
+
+                    for (resource, count) in world_map.stockpiles.totals().iter() {
+                        ui.text(&im_str!("{}: {}", resource, count));
+                    }
+                });
+
+            Window::new(im_str!("Farms"))
+                .size([300.0, 200.0], Condition::FirstUseEver)
+                .build(ui, || {
+                    ui.input_int3(im_str!("from##farm"), &mut self.farm_from)
+                        .build();
+                    ui.input_int3(im_str!("to##farm"), &mut self.farm_to)
+                        .build();
+
+                    for crop_id in crops.crop_ids() {
+                        if ui.button(&im_str!("plant {}", crop_id), [0.0, 0.0]) {
+                            let from = self.farm_from;
+                            let to = self.farm_to;
+                            if let Ok(crop) = crops.get(crop_id) {
+                                world_map.designate_farm(
+                                    Point3::new(from[0] as u32, from[1] as u32, from[2] as u32),
+                                    Point3::new(to[0] as u32, to[1] as u32, from[2] as u32),
+                                    crop_id,
+                                    crop,
+                                );
+                            }
+                        }
+                    }
+
+                    ui.separator();
+
+                    let mut removed = None;
+                    for plot in world_map.farms.iter() {
+                        ui.text(&im_str!(
+                            "Farm {}: {}, {}/{} tiles tilled",
+                            plot.id,
+                            plot.crop,
+                            plot.tilled.len(),
+                            plot.tiles.len()
+                        ));
+                        if ui.button(&im_str!("Remove##farm{}", plot.id), [0.0, 0.0]) {
+                            removed = Some(plot.id);
+                        }
+                    }
+                    if let Some(id) = removed {
+                        world_map.farms.remove(id);
+                    }
+                });
+
+            Window::new(im_str!("Notifications"))
+                .size([300.0, 200.0], Condition::FirstUseEver)
+                .build(ui, || {
+                    for message in notifications.messages.iter() {
+                        ui.text(message);
+                    }
+                });
+
             Window::new(im_str!("Hover"))
                 .size([300.0, 100.0], Condition::FirstUseEver)
                 .build(ui, || {